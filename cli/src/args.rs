@@ -0,0 +1,110 @@
+//! Command-line spellings of the program's enums, shared by argument parsing and output.
+
+use sprite_manager::state::{AttributeValue, EquipmentSlot, License, PerspectiveTags, StyleTags};
+
+const SLOTS: &[(&str, EquipmentSlot)] = &[
+    ("body", EquipmentSlot::Body),
+    ("hair", EquipmentSlot::Hair),
+    ("hat", EquipmentSlot::Hat),
+    ("top", EquipmentSlot::Top),
+    ("bottom", EquipmentSlot::Bottom),
+    ("shoes", EquipmentSlot::Shoes),
+    ("weapon", EquipmentSlot::Weapon),
+    ("offhand", EquipmentSlot::Offhand),
+    ("accessory", EquipmentSlot::Accessory),
+];
+
+const PERSPECTIVES: &[(&str, PerspectiveTags)] = &[
+    ("rpg", PerspectiveTags::RPG),
+    ("top-down", PerspectiveTags::TopDown),
+    ("side-scroller", PerspectiveTags::SideScroller),
+    ("platformer", PerspectiveTags::Platformer),
+];
+
+const STYLES: &[(&str, StyleTags)] = &[
+    ("pixel", StyleTags::Pixel),
+    ("vector", StyleTags::Vector),
+    ("hand-drawn", StyleTags::HandDrawn),
+    ("cartoon", StyleTags::Cartoon),
+];
+
+fn lookup<T: Clone>(table: &[(&str, T)], kind: &str, value: &str) -> Result<T, String> {
+    table
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(value))
+        .map(|(_, item)| item.clone())
+        .ok_or_else(|| {
+            let names: Vec<&str> = table.iter().map(|(name, _)| *name).collect();
+            format!(
+                "unknown {} `{}`, expected one of {}",
+                kind,
+                value,
+                names.join(", ")
+            )
+        })
+}
+
+fn name_of<T: PartialEq>(table: &'static [(&'static str, T)], item: &T) -> &'static str {
+    table
+        .iter()
+        .find(|(_, candidate)| candidate == item)
+        .map(|(name, _)| *name)
+        .unwrap_or("unknown")
+}
+
+pub fn parse_slot(value: &str) -> Result<EquipmentSlot, String> {
+    lookup(SLOTS, "slot", value)
+}
+
+pub fn parse_perspective(value: &str) -> Result<PerspectiveTags, String> {
+    lookup(PERSPECTIVES, "perspective", value)
+}
+
+pub fn parse_style(value: &str) -> Result<StyleTags, String> {
+    lookup(STYLES, "style", value)
+}
+
+/// Parses `cc0`, `cc-by`, `cc-by-nc` or `proprietary`; anything containing `://` is taken as the
+/// URI of custom license terms.
+pub fn parse_license(value: &str) -> Result<License, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "cc0" => Ok(License::Cc0),
+        "cc-by" => Ok(License::CcBy),
+        "cc-by-nc" => Ok(License::CcByNc),
+        "proprietary" => Ok(License::Proprietary),
+        _ if value.contains("://") => Ok(License::CustomUri(value.to_string())),
+        _ => Err(format!(
+            "unknown license `{}`, expected cc0, cc-by, cc-by-nc, proprietary or a URI",
+            value
+        )),
+    }
+}
+
+/// Parses `key=value`. Values reading as `true`/`false` or an integer are stored typed, e.g.
+/// `season=3` becomes an integer while `rarity=epic` stays a string.
+pub fn parse_attribute(value: &str) -> Result<(String, AttributeValue), String> {
+    let (key, raw) = value
+        .split_once('=')
+        .ok_or_else(|| format!("attribute `{}` should be written as key=value", value))?;
+    let value = match raw {
+        "true" => AttributeValue::Bool(true),
+        "false" => AttributeValue::Bool(false),
+        _ => match raw.parse::<i64>() {
+            Ok(integer) => AttributeValue::Integer(integer),
+            Err(_) => AttributeValue::String(raw.to_string()),
+        },
+    };
+    Ok((key.to_string(), value))
+}
+
+pub fn slot_name(slot: &EquipmentSlot) -> &'static str {
+    name_of(SLOTS, slot)
+}
+
+pub fn perspective_name(perspective: &PerspectiveTags) -> &'static str {
+    name_of(PERSPECTIVES, perspective)
+}
+
+pub fn style_name(style: &StyleTags) -> &'static str {
+    name_of(STYLES, style)
+}
@@ -0,0 +1,435 @@
+//! Import sprite metadata from an Aseprite JSON export.
+//!
+//! Aseprite's "Export Sprite Sheet" writes a JSON file next to the image, with `frames` as either
+//! an array or a hash keyed by frame name. [`AsepriteSheet`] reads both, and
+//! [`AsepriteSheet::store_sprite_args`] turns the sheet into [`StoreSpriteArgs`] ready for
+//! [`StoreSpriteBuilder`](sprite_manager::builders::StoreSpriteBuilder):
+//!
+//! - the name is the image file stem, e.g. `hat` for `hat.png`;
+//! - the frame size, frame count and, when every frame lasts as long, the frame duration become
+//!   `frame_width`, `frame_height`, `frame_count` and `frame_ms` attributes;
+//! - each frame tag becomes an `anim.<tag>` attribute such as `"0-3"` or `"4-7 pingpong"`;
+//! - each slice with a pivot becomes a `pivot.<slice>` attribute such as `"8,15"`, measured from
+//!   the frame's top-left corner;
+//! - comma-separated user data of layers, tags and slices becomes `custom_tags`.
+//!
+//! The individual frame rectangles stay off chain: a sprite holds at most
+//! [`MAX_ATTRIBUTES`] attributes, so they are only available from [`AsepriteSheet::frames`].
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{
+    de::{MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use sprite_manager::{
+    instruction::StoreSpriteArgs,
+    state::{AttributeValue, StyleTags, MAX_ATTRIBUTES, MAX_ATTRIBUTE_KEY_LEN},
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AsepriteError {
+    #[error("Failed to read {}: {}", .0.display(), .1)]
+    Read(PathBuf, #[source] io::Error),
+
+    #[error("Invalid Aseprite JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("The sheet has no frames")]
+    NoFrames,
+
+    #[error("Tag `{0}` covers frames outside the sheet")]
+    TagOutOfRange(String),
+
+    #[error("Tag `{0}` has unknown direction `{1}`")]
+    UnknownDirection(String, String),
+
+    #[error("Attribute `{0}` is defined twice, rename the tag or slice")]
+    DuplicateAttribute(String),
+
+    #[error("Attribute key `{0}` is longer than {max} bytes", max = MAX_ATTRIBUTE_KEY_LEN)]
+    AttributeKeyTooLong(String),
+
+    #[error("The sheet needs {0} attributes, a sprite holds at most {max}", max = MAX_ATTRIBUTES)]
+    TooManyAttributes(usize),
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub w: u32,
+    pub h: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// The frame's file name in the export, e.g. `hat 0.aseprite`.
+    pub name: String,
+    /// Where the frame sits in the sheet image.
+    pub rect: Rect,
+    pub duration_ms: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Reverse,
+    PingPong,
+    PingPongReverse,
+}
+
+impl Direction {
+    fn parse(tag: &str, value: &str) -> Result<Self, AsepriteError> {
+        match value {
+            "" | "forward" => Ok(Direction::Forward),
+            "reverse" => Ok(Direction::Reverse),
+            "pingpong" => Ok(Direction::PingPong),
+            "pingpong_reverse" => Ok(Direction::PingPongReverse),
+            _ => Err(AsepriteError::UnknownDirection(
+                tag.to_string(),
+                value.to_string(),
+            )),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Direction::Forward => "forward",
+            Direction::Reverse => "reverse",
+            Direction::PingPong => "pingpong",
+            Direction::PingPongReverse => "pingpong_reverse",
+        }
+    }
+}
+
+/// A frame tag: the inclusive frame range of one animation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Animation {
+    pub name: String,
+    pub from: u32,
+    pub to: u32,
+    pub direction: Direction,
+}
+
+/// The pivot of a slice, relative to the top-left corner of the frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pivot {
+    pub slice: String,
+    /// First frame the slice key applies to.
+    pub frame: u32,
+    pub x: i32,
+    pub y: i32,
+}
+
+/// The parts of an Aseprite JSON export that describe a sprite.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsepriteSheet {
+    /// Sheet image path as written by Aseprite.
+    pub image: String,
+    /// Sheet image size in pixels, when the export records it.
+    pub size: Option<(u32, u32)>,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub frames: Vec<Frame>,
+    pub animations: Vec<Animation>,
+    pub pivots: Vec<Pivot>,
+    /// User data of layers, tags and slices, in that order.
+    pub user_data: Vec<String>,
+}
+
+impl AsepriteSheet {
+    /// Reads and parses the JSON export at `path`.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, AsepriteError> {
+        let path = path.as_ref();
+        let json =
+            fs::read_to_string(path).map_err(|err| AsepriteError::Read(path.to_owned(), err))?;
+        Self::from_json(&json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, AsepriteError> {
+        let raw: RawSheet = serde_json::from_str(json)?;
+
+        let frames: Vec<(String, RawFrame)> = match raw.frames {
+            RawFrames::Array(frames) => frames
+                .into_iter()
+                .map(|frame| (frame.filename.clone(), frame))
+                .collect(),
+            RawFrames::Hash(OrderedFrames(frames)) => frames,
+        };
+        // Trimmed frames are smaller than the canvas, which `sourceSize` keeps.
+        let first = frames.first().ok_or(AsepriteError::NoFrames)?;
+        let (frame_width, frame_height) = match first.1.source_size {
+            Some(size) => (size.w, size.h),
+            None => (first.1.frame.w, first.1.frame.h),
+        };
+        let frames: Vec<Frame> = frames
+            .into_iter()
+            .map(|(name, frame)| Frame {
+                name,
+                rect: frame.frame,
+                duration_ms: frame.duration,
+            })
+            .collect();
+
+        let animations = raw
+            .meta
+            .frame_tags
+            .iter()
+            .map(|tag| {
+                if tag.from > tag.to || tag.to as usize >= frames.len() {
+                    return Err(AsepriteError::TagOutOfRange(tag.name.clone()));
+                }
+                Ok(Animation {
+                    name: tag.name.clone(),
+                    from: tag.from,
+                    to: tag.to,
+                    direction: Direction::parse(&tag.name, &tag.direction)?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let pivots = raw
+            .meta
+            .slices
+            .iter()
+            .filter_map(|slice| {
+                let key = slice.keys.first()?;
+                let pivot = key.pivot?;
+                Some(Pivot {
+                    slice: slice.name.clone(),
+                    frame: key.frame,
+                    x: key.bounds.x + pivot.x,
+                    y: key.bounds.y + pivot.y,
+                })
+            })
+            .collect();
+
+        let user_data = raw
+            .meta
+            .layers
+            .into_iter()
+            .map(|layer| layer.data)
+            .chain(raw.meta.frame_tags.into_iter().map(|tag| tag.data))
+            .chain(raw.meta.slices.into_iter().map(|slice| slice.data))
+            .flatten()
+            .collect();
+
+        Ok(Self {
+            image: raw.meta.image,
+            size: raw.meta.size.map(|size| (size.w, size.h)),
+            frame_width,
+            frame_height,
+            frames,
+            animations,
+            pivots,
+            user_data,
+        })
+    }
+
+    /// The image file stem, or an empty string when the export names no image.
+    pub fn name(&self) -> String {
+        Path::new(&self.image)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// User data split on commas, trimmed and without duplicates.
+    pub fn custom_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = vec![];
+        for tag in self.user_data.iter().flat_map(|data| data.split(',')) {
+            let tag = tag.trim();
+            if !tag.is_empty() && !tags.iter().any(|existing| existing == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
+
+    pub fn attributes(&self) -> Result<Vec<(String, AttributeValue)>, AsepriteError> {
+        let mut attributes = vec![
+            (
+                "frame_width".to_string(),
+                AttributeValue::Integer(self.frame_width.into()),
+            ),
+            (
+                "frame_height".to_string(),
+                AttributeValue::Integer(self.frame_height.into()),
+            ),
+            (
+                "frame_count".to_string(),
+                AttributeValue::Integer(self.frames.len() as i64),
+            ),
+        ];
+        let duration = self
+            .frames
+            .first()
+            .ok_or(AsepriteError::NoFrames)?
+            .duration_ms;
+        if self
+            .frames
+            .iter()
+            .all(|frame| frame.duration_ms == duration)
+        {
+            attributes.push((
+                "frame_ms".to_string(),
+                AttributeValue::Integer(duration.into()),
+            ));
+        }
+
+        for animation in &self.animations {
+            let mut range = format!("{}-{}", animation.from, animation.to);
+            if animation.direction != Direction::Forward {
+                range = format!("{} {}", range, animation.direction.name());
+            }
+            attributes.push((
+                format!("anim.{}", animation.name),
+                AttributeValue::String(range),
+            ));
+        }
+        for pivot in &self.pivots {
+            attributes.push((
+                format!("pivot.{}", pivot.slice),
+                AttributeValue::String(format!("{},{}", pivot.x, pivot.y)),
+            ));
+        }
+
+        for (index, (key, _)) in attributes.iter().enumerate() {
+            if key.len() > MAX_ATTRIBUTE_KEY_LEN {
+                return Err(AsepriteError::AttributeKeyTooLong(key.clone()));
+            }
+            if attributes[..index].iter().any(|(other, _)| other == key) {
+                return Err(AsepriteError::DuplicateAttribute(key.clone()));
+            }
+        }
+        if attributes.len() > MAX_ATTRIBUTES {
+            return Err(AsepriteError::TooManyAttributes(attributes.len()));
+        }
+        Ok(attributes)
+    }
+
+    /// Arguments for a pixel-art body sprite described by the sheet. Override the slot, license
+    /// and other fields Aseprite knows nothing about with struct update syntax.
+    pub fn store_sprite_args(&self) -> Result<StoreSpriteArgs, AsepriteError> {
+        Ok(StoreSpriteArgs {
+            name: self.name(),
+            style_tags: vec![StyleTags::Pixel],
+            custom_tags: self.custom_tags(),
+            attributes: self.attributes()?,
+            ..StoreSpriteArgs::default()
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct RawSheet {
+    frames: RawFrames,
+    meta: RawMeta,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawFrames {
+    Array(Vec<RawFrame>),
+    Hash(OrderedFrames),
+}
+
+/// The hash form of `frames`, kept in file order since it is the frame order.
+struct OrderedFrames(Vec<(String, RawFrame)>);
+
+impl<'de> Deserialize<'de> for OrderedFrames {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = OrderedFrames;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of frame names to frames")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut frames = vec![];
+                while let Some(entry) = map.next_entry()? {
+                    frames.push(entry);
+                }
+                Ok(OrderedFrames(frames))
+            }
+        }
+
+        deserializer.deserialize_map(FramesVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawFrame {
+    #[serde(default)]
+    filename: String,
+    frame: Rect,
+    #[serde(default)]
+    duration: u32,
+    #[serde(rename = "sourceSize")]
+    source_size: Option<RawSize>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct RawSize {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawMeta {
+    #[serde(default)]
+    image: String,
+    size: Option<RawSize>,
+    #[serde(default)]
+    frame_tags: Vec<RawTag>,
+    #[serde(default)]
+    layers: Vec<RawLayer>,
+    #[serde(default)]
+    slices: Vec<RawSlice>,
+}
+
+#[derive(Deserialize)]
+struct RawTag {
+    name: String,
+    from: u32,
+    to: u32,
+    #[serde(default)]
+    direction: String,
+    data: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawLayer {
+    data: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawSlice {
+    name: String,
+    data: Option<String>,
+    #[serde(default)]
+    keys: Vec<RawSliceKey>,
+}
+
+#[derive(Deserialize)]
+struct RawSliceKey {
+    #[serde(default)]
+    frame: u32,
+    bounds: Rect,
+    pivot: Option<RawPoint>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct RawPoint {
+    x: i32,
+    y: i32,
+}
@@ -0,0 +1,477 @@
+//! The `sprite-manager` command-line tool.
+//!
+//! [`run`] executes a parsed [`Command`] through a [`SpriteManagerClient`], so the same code path
+//! serves an RPC endpoint from `main` and a `solana-program-test` bank in the integration tests.
+
+mod args;
+pub mod aseprite;
+mod output;
+
+pub use output::{Output, OutputFormat};
+
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+use sprite_manager::{
+    builders::{
+        EquipSpriteBuilder, SetActiveVariantBuilder, SetPaletteVariantBuilder, UnequipSpriteBuilder,
+    },
+    instruction::StoreSpriteArgs,
+    pda::find_sprite_address,
+    state::{
+        AttributeValue, ConfigLimits, EquipmentSlot, License, PerspectiveTags, StyleTags,
+        MAX_ATTRIBUTES, MAX_ATTRIBUTE_KEY_LEN, MAX_ATTRIBUTE_VALUE_LEN,
+    },
+};
+use sprite_manager_client::{ClientError, Connection, SpriteManagerClient};
+use thiserror::Error;
+
+use crate::{
+    args::{parse_attribute, parse_license, parse_perspective, parse_slot, parse_style},
+    aseprite::{AsepriteError, AsepriteSheet},
+};
+
+#[derive(Parser, Debug)]
+#[clap(
+    name = "sprite-manager",
+    version,
+    about = "Manage sprites held by base NFTs"
+)]
+pub struct Cli {
+    /// JSON RPC URL, defaults to the one in the Solana CLI config
+    #[clap(short, long, global = true)]
+    pub url: Option<String>,
+
+    /// Keypair file that signs and pays, defaults to the one in the Solana CLI config
+    #[clap(short, long, global = true)]
+    pub keypair: Option<String>,
+
+    /// Print human-readable text or JSON
+    #[clap(long, value_enum, default_value = "display", global = true)]
+    pub output: OutputFormat,
+
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Command {
+    /// Create the sprite account of a base NFT
+    Create {
+        #[clap(long)]
+        base_mint: Pubkey,
+
+        /// Token account holding the base NFT, the signer's associated token account by default
+        #[clap(long)]
+        base_token_account: Option<Pubkey>,
+    },
+
+    /// Move a sprite token into a base NFT's escrow and record its metadata
+    Store(StoreCommand),
+
+    /// List the sprites stored under a base NFT
+    List {
+        #[clap(long)]
+        base_mint: Pubkey,
+
+        /// Only list the equipped sprites, in draw order
+        #[clap(long)]
+        equipped: bool,
+    },
+
+    /// Show a sprite account, or a single sprite stored in it
+    Show {
+        #[clap(long)]
+        base_mint: Pubkey,
+
+        #[clap(long)]
+        sprite_mint: Option<Pubkey>,
+    },
+
+    /// Equip, unequip or recolor a stored sprite, or make it the shown variant of its family
+    Update(UpdateCommand),
+}
+
+impl Command {
+    pub fn requires_signer(&self) -> bool {
+        !matches!(self, Command::List { .. } | Command::Show { .. })
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct StoreCommand {
+    #[clap(long)]
+    pub base_mint: Pubkey,
+
+    #[clap(long)]
+    pub sprite_mint: Pubkey,
+
+    /// Token account holding the sprite, the signer's associated token account by default
+    #[clap(long)]
+    pub sprite_token_account: Option<Pubkey>,
+
+    /// Defaults to the image name of the Aseprite sheet
+    #[clap(long, required_unless_present = "aseprite")]
+    pub name: Option<String>,
+
+    #[clap(long, default_value = "")]
+    pub description: String,
+
+    #[clap(long, value_parser = parse_slot, default_value = "body")]
+    pub slot: EquipmentSlot,
+
+    #[clap(long, default_value_t = 0)]
+    pub z_order: u8,
+
+    /// rpg, top-down, side-scroller or platformer, repeatable
+    #[clap(long = "perspective", value_parser = parse_perspective)]
+    pub perspective_tags: Vec<PerspectiveTags>,
+
+    /// pixel, vector, hand-drawn or cartoon, repeatable
+    #[clap(long = "style", value_parser = parse_style)]
+    pub style_tags: Vec<StyleTags>,
+
+    /// Free-form tag, repeatable
+    #[clap(long = "tag")]
+    pub custom_tags: Vec<String>,
+
+    /// Typed key=value attribute such as rarity=epic or season=3, repeatable
+    #[clap(long = "attribute", value_parser = parse_attribute)]
+    pub attributes: Vec<(String, AttributeValue)>,
+
+    /// cc0, cc-by, cc-by-nc, proprietary or the URI of custom terms
+    #[clap(long, value_parser = parse_license, default_value = "proprietary")]
+    pub license: License,
+
+    #[clap(long)]
+    pub attribution: Option<String>,
+
+    /// Family this sprite is a variant of
+    #[clap(long)]
+    pub family: Option<Pubkey>,
+
+    /// Aseprite JSON export to take animation, pivot and frame attributes and user data tags from
+    #[clap(long, value_parser = |path: &str| AsepriteSheet::from_path(path))]
+    pub aseprite: Option<AsepriteSheet>,
+}
+
+#[derive(Args, Debug)]
+#[clap(group(
+    ArgGroup::new("change")
+        .required(true)
+        .args(&["equip", "unequip", "activate", "palette-variant", "base-palette"]),
+))]
+pub struct UpdateCommand {
+    #[clap(long)]
+    pub base_mint: Pubkey,
+
+    #[clap(long)]
+    pub sprite_mint: Pubkey,
+
+    /// Token account holding the base NFT, the signer's associated token account by default
+    #[clap(long)]
+    pub base_token_account: Option<Pubkey>,
+
+    /// Wear the sprite in its slot
+    #[clap(long)]
+    pub equip: bool,
+
+    /// Take the sprite off
+    #[clap(long)]
+    pub unequip: bool,
+
+    /// Show this sprite in place of the other variants of its family
+    #[clap(long)]
+    pub activate: bool,
+
+    /// Draw the sprite with the named palette variant
+    #[clap(long)]
+    pub palette_variant: Option<String>,
+
+    /// Draw the sprite with its base palette
+    #[clap(long)]
+    pub base_palette: bool,
+}
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+
+    #[error("Failed to read keypair {0}: {1}")]
+    Keypair(String, String),
+
+    #[error("This command needs a keypair to sign with")]
+    MissingSigner,
+
+    #[error("No sprite account exists for base mint {0}")]
+    SpriteAccountNotFound(Pubkey),
+
+    #[error("Sprite {0} is not stored in this sprite account")]
+    SpriteNotFound(Pubkey),
+
+    #[error(transparent)]
+    Aseprite(#[from] AsepriteError),
+
+    #[error("The sprite is over the program's limits: {0}")]
+    LimitExceeded(String),
+}
+
+pub async fn run<C: Connection + Send>(
+    client: &mut SpriteManagerClient<C>,
+    signer: Option<&Keypair>,
+    command: Command,
+) -> Result<Output, CliError> {
+    match command {
+        Command::Create {
+            base_mint,
+            base_token_account,
+        } => {
+            let signer = signer.ok_or(CliError::MissingSigner)?;
+            let base_token_account = base_token_account
+                .unwrap_or_else(|| get_associated_token_address(&signer.pubkey(), &base_mint));
+            let signature = client
+                .create_sprite_account(signer, &base_mint, &base_token_account)
+                .await?;
+            Ok(Output::Transaction {
+                sprite_account: find_sprite_address(&base_mint).0,
+                signature,
+            })
+        }
+        Command::Store(store) => {
+            let signer = signer.ok_or(CliError::MissingSigner)?;
+            let (base_mint, sprite_mint) = (store.base_mint, store.sprite_mint);
+            let sprite_token_account = store
+                .sprite_token_account
+                .unwrap_or_else(|| get_associated_token_address(&signer.pubkey(), &sprite_mint));
+            let args = store_sprite_args(store)?;
+            let limits = client
+                .get_program_config()
+                .await?
+                .map(|config| config.limits)
+                .unwrap_or_default();
+            check_limits(&args, &limits)?;
+            let signature = client
+                .store_sprite(
+                    signer,
+                    &base_mint,
+                    &sprite_mint,
+                    &sprite_token_account,
+                    args,
+                )
+                .await?;
+            Ok(Output::Transaction {
+                sprite_account: find_sprite_address(&base_mint).0,
+                signature,
+            })
+        }
+        Command::List {
+            base_mint,
+            equipped,
+        } => {
+            let account = fetch_sprite_account(client, &base_mint).await?;
+            let sprites = if equipped {
+                account.loadout().into_iter().cloned().collect()
+            } else {
+                account.sprites
+            };
+            Ok(Output::Sprites(sprites))
+        }
+        Command::Show {
+            base_mint,
+            sprite_mint,
+        } => {
+            let account = fetch_sprite_account(client, &base_mint).await?;
+            match sprite_mint {
+                Some(sprite_mint) => account
+                    .find_sprite(&sprite_mint)
+                    .cloned()
+                    .map(Output::Sprite)
+                    .ok_or(CliError::SpriteNotFound(sprite_mint)),
+                None => Ok(Output::Account {
+                    address: find_sprite_address(&base_mint).0,
+                    account,
+                }),
+            }
+        }
+        Command::Update(update) => {
+            let signer = signer.ok_or(CliError::MissingSigner)?;
+            let instruction = update_instruction(&update, signer.pubkey());
+            let signature = client.send(&[instruction], signer).await?;
+            Ok(Output::Transaction {
+                sprite_account: find_sprite_address(&update.base_mint).0,
+                signature,
+            })
+        }
+    }
+}
+
+/// Flags win over the Aseprite sheet: an explicit name or style replaces the sheet's, while tags
+/// and attributes from both are combined.
+pub fn store_sprite_args(store: StoreCommand) -> Result<StoreSpriteArgs, CliError> {
+    let args = StoreSpriteArgs {
+        name: store.name.unwrap_or_default(),
+        description: store.description,
+        perspective_tags: store.perspective_tags,
+        style_tags: store.style_tags,
+        custom_tags: store.custom_tags,
+        attributes: store.attributes,
+        slot: store.slot,
+        z_order: store.z_order,
+        license: store.license,
+        attribution: store.attribution,
+        family: store.family,
+        ..StoreSpriteArgs::default()
+    };
+    let sheet = match store.aseprite {
+        Some(sheet) => sheet.store_sprite_args()?,
+        None => return Ok(args),
+    };
+
+    let mut custom_tags = args.custom_tags;
+    for tag in sheet.custom_tags {
+        if !custom_tags.contains(&tag) {
+            custom_tags.push(tag);
+        }
+    }
+    let mut attributes = args.attributes;
+    attributes.extend(
+        sheet
+            .attributes
+            .into_iter()
+            .filter(|(key, _)| !attributes.iter().any(|(existing, _)| existing == key))
+            .collect::<Vec<_>>(),
+    );
+
+    Ok(StoreSpriteArgs {
+        name: if args.name.is_empty() {
+            sheet.name
+        } else {
+            args.name
+        },
+        style_tags: if args.style_tags.is_empty() {
+            sheet.style_tags
+        } else {
+            args.style_tags
+        },
+        custom_tags,
+        attributes,
+        ..args
+    })
+}
+
+/// Runs the program's size checks on `args`, so a sprite the program would reject fails here
+/// rather than in a fee-bearing transaction.
+pub fn check_limits(args: &StoreSpriteArgs, limits: &ConfigLimits) -> Result<(), CliError> {
+    let over = |what: String, max: usize| {
+        Err(CliError::LimitExceeded(format!(
+            "{}, at most {}",
+            what, max
+        )))
+    };
+    if args.name.len() > limits.max_name_len as usize {
+        return over(
+            format!("name is {} bytes", args.name.len()),
+            limits.max_name_len.into(),
+        );
+    }
+    if args.description.len() > limits.max_description_len as usize {
+        return over(
+            format!("description is {} bytes", args.description.len()),
+            limits.max_description_len.into(),
+        );
+    }
+    if args.custom_tags.len() > limits.max_custom_tags as usize {
+        return over(
+            format!("{} custom tags", args.custom_tags.len()),
+            limits.max_custom_tags.into(),
+        );
+    }
+    if let Some(tag) = args
+        .custom_tags
+        .iter()
+        .find(|tag| tag.len() > limits.max_custom_tag_len as usize)
+    {
+        return over(
+            format!("custom tag `{}` is {} bytes", tag, tag.len()),
+            limits.max_custom_tag_len.into(),
+        );
+    }
+    if args.attributes.len() > MAX_ATTRIBUTES {
+        return over(
+            format!("{} attributes", args.attributes.len()),
+            MAX_ATTRIBUTES,
+        );
+    }
+    for (index, (key, value)) in args.attributes.iter().enumerate() {
+        if key.is_empty() || key.len() > MAX_ATTRIBUTE_KEY_LEN {
+            return over(
+                format!("attribute key `{}` is {} bytes", key, key.len()),
+                MAX_ATTRIBUTE_KEY_LEN,
+            );
+        }
+        if let AttributeValue::String(value) = value {
+            if value.len() > MAX_ATTRIBUTE_VALUE_LEN {
+                return over(
+                    format!("attribute `{}` has a {} byte value", key, value.len()),
+                    MAX_ATTRIBUTE_VALUE_LEN,
+                );
+            }
+        }
+        if args.attributes[..index]
+            .iter()
+            .any(|(other, _)| other == key)
+        {
+            return Err(CliError::LimitExceeded(format!(
+                "attribute `{}` is given twice",
+                key
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn update_instruction(
+    update: &UpdateCommand,
+    owner: Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    let (base_mint, sprite_mint) = (update.base_mint, update.sprite_mint);
+    let base_token_account = update
+        .base_token_account
+        .unwrap_or_else(|| get_associated_token_address(&owner, &base_mint));
+
+    if update.equip {
+        EquipSpriteBuilder::new(base_mint, sprite_mint, owner)
+            .base_token_account(base_token_account)
+            .instruction()
+    } else if update.unequip {
+        UnequipSpriteBuilder::new(base_mint, sprite_mint, owner)
+            .base_token_account(base_token_account)
+            .instruction()
+    } else if update.activate {
+        SetActiveVariantBuilder::new(base_mint, sprite_mint, owner)
+            .base_token_account(base_token_account)
+            .instruction()
+    } else {
+        // `--palette-variant NAME`, or `--base-palette` which leaves it unset.
+        SetPaletteVariantBuilder::new(
+            base_mint,
+            sprite_mint,
+            owner,
+            update.palette_variant.clone(),
+        )
+        .base_token_account(base_token_account)
+        .instruction()
+    }
+}
+
+async fn fetch_sprite_account<C: Connection + Send>(
+    client: &mut SpriteManagerClient<C>,
+    base_mint: &Pubkey,
+) -> Result<sprite_manager::state::SpriteAccount, CliError> {
+    client
+        .get_sprite_account(base_mint)
+        .await?
+        .ok_or(CliError::SpriteAccountNotFound(*base_mint))
+}
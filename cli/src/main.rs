@@ -0,0 +1,41 @@
+use clap::Parser;
+use solana_cli_config::{Config, CONFIG_FILE};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file};
+use sprite_manager_cli::{run, Cli, CliError};
+use sprite_manager_client::SpriteManagerClient;
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    if let Err(err) = process(cli).await {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn process(cli: Cli) -> Result<(), CliError> {
+    let config = CONFIG_FILE
+        .as_ref()
+        .and_then(|path| Config::load(path).ok())
+        .unwrap_or_default();
+    let url = cli.url.unwrap_or(config.json_rpc_url);
+    let keypair_path = cli.keypair.unwrap_or(config.keypair_path);
+
+    // Read-only commands work without a keypair on disk.
+    let signer = if cli.command.requires_signer() {
+        Some(
+            read_keypair_file(&keypair_path)
+                .map_err(|err| CliError::Keypair(keypair_path, err.to_string()))?,
+        )
+    } else {
+        None
+    };
+
+    let rpc_client = RpcClient::new_with_commitment(url, CommitmentConfig::confirmed());
+    let mut client = SpriteManagerClient::new(rpc_client);
+    let output = run(&mut client, signer.as_ref(), cli.command).await?;
+    println!("{}", output.render(cli.output));
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use sprite_manager::state::{AttributeValue, Sprite, SpriteAccount, Timestamp};
+
+use crate::args::{perspective_name, slot_name, style_name};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Display,
+    Json,
+}
+
+/// The result of a command, printed either for humans or as JSON in the format of the `serde`
+/// feature of the program crate.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum Output {
+    Transaction {
+        sprite_account: Pubkey,
+        signature: Signature,
+    },
+    Account {
+        address: Pubkey,
+        account: SpriteAccount,
+    },
+    Sprites(Vec<Sprite>),
+    Sprite(Sprite),
+}
+
+impl Output {
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Display => self.to_string(),
+            OutputFormat::Json => serde_json::to_string_pretty(&self.to_json()).unwrap(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            Output::Transaction {
+                sprite_account,
+                signature,
+            } => json!({
+                "spriteAccount": sprite_account.to_string(),
+                "signature": signature.to_string(),
+            }),
+            Output::Account { address, account } => json!({
+                "address": address.to_string(),
+                "account": account,
+            }),
+            Output::Sprites(sprites) => json!(sprites),
+            Output::Sprite(sprite) => json!(sprite),
+        }
+    }
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Output::Transaction {
+                sprite_account,
+                signature,
+            } => {
+                writeln!(f, "Sprite account: {}", sprite_account)?;
+                write!(f, "Signature: {}", signature)
+            }
+            Output::Account { address, account } => {
+                writeln!(f, "Sprite account: {}", address)?;
+                writeln!(f, "Base mint: {}", account.base_mint)?;
+                writeln!(f, "Created: {}", timestamp(&account.created_at))?;
+                writeln!(f, "Updated: {}", timestamp(&account.updated_at))?;
+                for family in &account.families {
+                    writeln!(
+                        f,
+                        "Family {}: {} variants, showing {}",
+                        family.id, family.variant_count, family.active_variant
+                    )?;
+                }
+                write!(f, "Sprites: {}", account.sprites.len())?;
+                for sprite in &account.sprites {
+                    write!(f, "\n  {}", sprite_line(sprite))?;
+                }
+                Ok(())
+            }
+            Output::Sprites(sprites) if sprites.is_empty() => write!(f, "No sprites"),
+            Output::Sprites(sprites) => {
+                let lines: Vec<String> = sprites.iter().map(sprite_line).collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+            Output::Sprite(sprite) => write_sprite(f, sprite),
+        }
+    }
+}
+
+fn sprite_line(sprite: &Sprite) -> String {
+    format!(
+        "{} {:<9} z={:<3} {} {}",
+        sprite.mint,
+        slot_name(&sprite.slot),
+        sprite.z_order,
+        if sprite.equipped {
+            "equipped"
+        } else {
+            "        "
+        },
+        sprite.name
+    )
+}
+
+fn write_sprite(f: &mut fmt::Formatter, sprite: &Sprite) -> fmt::Result {
+    writeln!(f, "Name: {}", sprite.name)?;
+    writeln!(f, "Description: {}", sprite.description)?;
+    writeln!(f, "Mint: {}", sprite.mint)?;
+    writeln!(
+        f,
+        "Slot: {} (z-order {})",
+        slot_name(&sprite.slot),
+        sprite.z_order
+    )?;
+    writeln!(f, "Equipped: {}", sprite.equipped)?;
+    let perspectives: Vec<&str> = sprite
+        .perspective_tags
+        .iter()
+        .map(perspective_name)
+        .collect();
+    writeln!(f, "Perspectives: {}", perspectives.join(", "))?;
+    let styles: Vec<&str> = sprite.style_tags.iter().map(style_name).collect();
+    writeln!(f, "Styles: {}", styles.join(", "))?;
+    writeln!(f, "Tags: {}", sprite.custom_tags.join(", "))?;
+    for (key, value) in &sprite.attributes {
+        writeln!(f, "Attribute {}: {}", key, attribute_value(value))?;
+    }
+    if let Some(palette) = &sprite.palette {
+        let variants: Vec<&str> = palette.variants.iter().map(|v| v.name.as_str()).collect();
+        writeln!(
+            f,
+            "Palette: {} colors, variants {}, using {}",
+            palette.colors.len(),
+            variants.join(", "),
+            sprite.palette_variant.as_deref().unwrap_or("base palette")
+        )?;
+    }
+    if let Some(family) = &sprite.family {
+        writeln!(f, "Family: {}", family)?;
+    }
+    for rendition in &sprite.renditions {
+        writeln!(
+            f,
+            "Rendition {}x {:?} {}x{}: {}",
+            rendition.scale, rendition.format, rendition.width, rendition.height, rendition.uri
+        )?;
+    }
+    writeln!(f, "Artist: {}", sprite.artist)?;
+    writeln!(f, "License: {}", sprite.license)?;
+    if let Some(attribution) = &sprite.attribution {
+        writeln!(f, "Attribution: {}", attribution)?;
+    }
+    writeln!(f, "Created: {}", timestamp(&sprite.created_at))?;
+    write!(f, "Updated: {}", timestamp(&sprite.updated_at))
+}
+
+fn timestamp(timestamp: &Timestamp) -> String {
+    format!("{} (slot {})", timestamp.unix_timestamp, timestamp.slot)
+}
+
+fn attribute_value(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::String(value) => value.clone(),
+        AttributeValue::Integer(value) => value.to_string(),
+        AttributeValue::Bool(value) => value.to_string(),
+    }
+}
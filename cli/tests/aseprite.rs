@@ -0,0 +1,352 @@
+use borsh::BorshDeserialize;
+use clap::Parser;
+use solana_sdk::pubkey::Pubkey;
+use sprite_manager::{
+    builders::StoreSpriteBuilder,
+    instruction::{SpriteManagerInstruction, StoreSpriteArgs},
+    state::{AttributeValue, ConfigLimits, EquipmentSlot, StyleTags},
+};
+use sprite_manager_cli::{
+    aseprite::{AsepriteError, AsepriteSheet, Direction, Rect},
+    check_limits, store_sprite_args, Cli, CliError, Command,
+};
+
+/// A trimmed four-frame walk cycle exported with `frames` as an array.
+const ARRAY_SHEET: &str = r##"{
+  "frames": [
+    { "filename": "knight 0.aseprite", "frame": { "x": 0, "y": 0, "w": 14, "h": 16 },
+      "rotated": false, "trimmed": true,
+      "spriteSourceSize": { "x": 1, "y": 0, "w": 14, "h": 16 },
+      "sourceSize": { "w": 16, "h": 16 }, "duration": 100 },
+    { "filename": "knight 1.aseprite", "frame": { "x": 14, "y": 0, "w": 14, "h": 16 },
+      "rotated": false, "trimmed": true,
+      "spriteSourceSize": { "x": 1, "y": 0, "w": 14, "h": 16 },
+      "sourceSize": { "w": 16, "h": 16 }, "duration": 100 },
+    { "filename": "knight 2.aseprite", "frame": { "x": 28, "y": 0, "w": 14, "h": 16 },
+      "rotated": false, "trimmed": true,
+      "spriteSourceSize": { "x": 1, "y": 0, "w": 14, "h": 16 },
+      "sourceSize": { "w": 16, "h": 16 }, "duration": 100 },
+    { "filename": "knight 3.aseprite", "frame": { "x": 42, "y": 0, "w": 14, "h": 16 },
+      "rotated": false, "trimmed": true,
+      "spriteSourceSize": { "x": 1, "y": 0, "w": 14, "h": 16 },
+      "sourceSize": { "w": 16, "h": 16 }, "duration": 100 }
+  ],
+  "meta": {
+    "app": "https://www.aseprite.org/",
+    "version": "1.3",
+    "image": "sprites/knight.png",
+    "format": "RGBA8888",
+    "size": { "w": 56, "h": 16 },
+    "scale": "1",
+    "frameTags": [
+      { "name": "idle", "from": 0, "to": 1, "direction": "forward", "data": "knight, armor" },
+      { "name": "walk", "from": 2, "to": 3, "direction": "pingpong" }
+    ],
+    "layers": [
+      { "name": "body", "opacity": 255, "blendMode": "normal", "data": "armor,  steel " }
+    ],
+    "slices": [
+      { "name": "feet", "color": "#0000ffff", "data": "grounded",
+        "keys": [{ "frame": 0, "bounds": { "x": 4, "y": 12, "w": 8, "h": 4 },
+                   "pivot": { "x": 4, "y": 3 } }] },
+      { "name": "hitbox", "color": "#ff0000ff",
+        "keys": [{ "frame": 0, "bounds": { "x": 2, "y": 0, "w": 12, "h": 16 } }] }
+    ]
+  }
+}"##;
+
+/// The same kind of export with `frames` as a hash and uneven durations.
+const HASH_SHEET: &str = r#"{
+  "frames": {
+    "slime 2.png": { "frame": { "x": 0, "y": 8, "w": 8, "h": 8 },
+                     "sourceSize": { "w": 8, "h": 8 }, "duration": 200 },
+    "slime 0.png": { "frame": { "x": 0, "y": 0, "w": 8, "h": 8 },
+                     "sourceSize": { "w": 8, "h": 8 }, "duration": 100 },
+    "slime 1.png": { "frame": { "x": 8, "y": 0, "w": 8, "h": 8 },
+                     "sourceSize": { "w": 8, "h": 8 }, "duration": 100 }
+  },
+  "meta": {
+    "image": "slime.png",
+    "size": { "w": 16, "h": 16 },
+    "frameTags": [{ "name": "bounce", "from": 0, "to": 2, "direction": "reverse" }]
+  }
+}"#;
+
+fn string(value: &str) -> AttributeValue {
+    AttributeValue::String(value.to_string())
+}
+
+/// An export with `tags` frame tags over a single frame.
+fn sheet_with_tags(tags: &[&str]) -> String {
+    let tags: Vec<String> = tags
+        .iter()
+        .map(|name| format!(r#"{{ "name": "{}", "from": 0, "to": 0 }}"#, name))
+        .collect();
+    format!(
+        r#"{{ "frames": [{{ "frame": {{ "x": 0, "y": 0, "w": 8, "h": 8 }}, "duration": 100 }}],
+             "meta": {{ "image": "tile.png", "frameTags": [{}] }} }}"#,
+        tags.join(",")
+    )
+}
+
+#[test]
+fn test_array_sheet() {
+    let sheet = AsepriteSheet::from_json(ARRAY_SHEET).unwrap();
+
+    assert_eq!(sheet.name(), "knight");
+    assert_eq!(sheet.size, Some((56, 16)));
+    assert_eq!((sheet.frame_width, sheet.frame_height), (16, 16));
+    assert_eq!(sheet.frames.len(), 4);
+    assert_eq!(sheet.frames[1].name, "knight 1.aseprite");
+    assert_eq!(
+        sheet.frames[1].rect,
+        Rect {
+            x: 14,
+            y: 0,
+            w: 14,
+            h: 16
+        }
+    );
+    assert_eq!(sheet.animations[1].direction, Direction::PingPong);
+    assert_eq!(
+        sheet.custom_tags(),
+        vec!["armor", "steel", "knight", "grounded"]
+    );
+
+    assert_eq!(
+        sheet.attributes().unwrap(),
+        vec![
+            ("frame_width".to_string(), AttributeValue::Integer(16)),
+            ("frame_height".to_string(), AttributeValue::Integer(16)),
+            ("frame_count".to_string(), AttributeValue::Integer(4)),
+            ("frame_ms".to_string(), AttributeValue::Integer(100)),
+            ("anim.idle".to_string(), string("0-1")),
+            ("anim.walk".to_string(), string("2-3 pingpong")),
+            ("pivot.feet".to_string(), string("8,15")),
+        ]
+    );
+}
+
+#[test]
+fn test_hash_sheet_keeps_file_order() {
+    let sheet = AsepriteSheet::from_json(HASH_SHEET).unwrap();
+
+    let names: Vec<&str> = sheet
+        .frames
+        .iter()
+        .map(|frame| frame.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["slime 2.png", "slime 0.png", "slime 1.png"]);
+    assert!(sheet.custom_tags().is_empty());
+
+    // Durations differ, so there is no `frame_ms`.
+    let attributes = sheet.attributes().unwrap();
+    assert_eq!(attributes.len(), 4);
+    assert_eq!(
+        attributes[3],
+        ("anim.bounce".to_string(), string("0-2 reverse"))
+    );
+}
+
+#[test]
+fn test_invalid_sheets_are_rejected() {
+    assert!(matches!(
+        AsepriteSheet::from_json("{}"),
+        Err(AsepriteError::Json(_))
+    ));
+    assert!(matches!(
+        AsepriteSheet::from_json(r#"{ "frames": [], "meta": {} }"#),
+        Err(AsepriteError::NoFrames)
+    ));
+
+    let out_of_range = HASH_SHEET.replace(r#""to": 2"#, r#""to": 3"#);
+    assert!(matches!(
+        AsepriteSheet::from_json(&out_of_range),
+        Err(AsepriteError::TagOutOfRange(tag)) if tag == "bounce"
+    ));
+    let unknown_direction = HASH_SHEET.replace("reverse", "sideways");
+    assert!(matches!(
+        AsepriteSheet::from_json(&unknown_direction),
+        Err(AsepriteError::UnknownDirection(_, direction)) if direction == "sideways"
+    ));
+}
+
+#[test]
+fn test_sheet_from_path() {
+    let path = std::env::temp_dir().join(format!("aseprite-{}.json", Pubkey::new_unique()));
+    assert!(matches!(
+        AsepriteSheet::from_path(&path),
+        Err(AsepriteError::Read(missing, _)) if missing == path
+    ));
+
+    std::fs::write(&path, HASH_SHEET).unwrap();
+    let sheet = AsepriteSheet::from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(sheet.name(), "slime");
+
+    // A sheet emptied after parsing has no frame duration to report.
+    let mut sheet = sheet;
+    sheet.frames.clear();
+    assert!(matches!(sheet.attributes(), Err(AsepriteError::NoFrames)));
+}
+
+#[test]
+fn test_attributes_respect_sprite_limits() {
+    let sheet = AsepriteSheet::from_json(&sheet_with_tags(&["idle", "idle"])).unwrap();
+    assert!(matches!(
+        sheet.attributes(),
+        Err(AsepriteError::DuplicateAttribute(key)) if key == "anim.idle"
+    ));
+
+    let long_name = "a".repeat(32);
+    let sheet = AsepriteSheet::from_json(&sheet_with_tags(&[&long_name])).unwrap();
+    assert!(matches!(
+        sheet.attributes(),
+        Err(AsepriteError::AttributeKeyTooLong(_))
+    ));
+
+    // Four frame attributes plus twelve tags fit, a thirteenth does not.
+    let names: Vec<String> = (0..13).map(|index| format!("tag{}", index)).collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    let sheet = AsepriteSheet::from_json(&sheet_with_tags(&names[..12])).unwrap();
+    assert_eq!(sheet.attributes().unwrap().len(), 16);
+    let sheet = AsepriteSheet::from_json(&sheet_with_tags(&names)).unwrap();
+    assert!(matches!(
+        sheet.attributes(),
+        Err(AsepriteError::TooManyAttributes(17))
+    ));
+}
+
+#[test]
+fn test_store_sprite_args_feed_the_builder() {
+    let sheet = AsepriteSheet::from_json(ARRAY_SHEET).unwrap();
+    let args = StoreSpriteArgs {
+        slot: EquipmentSlot::Top,
+        ..sheet.store_sprite_args().unwrap()
+    };
+    assert_eq!(args.name, "knight");
+    assert_eq!(args.style_tags, vec![StyleTags::Pixel]);
+
+    let (base_mint, sprite_mint, owner) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let instruction =
+        StoreSpriteBuilder::new(base_mint, sprite_mint, owner, args.clone()).instruction();
+    match SpriteManagerInstruction::try_from_slice(&instruction.data).unwrap() {
+        SpriteManagerInstruction::StoreSprite(decoded) => assert_eq!(decoded, args),
+        instruction => panic!("unexpected instruction {:?}", instruction),
+    }
+}
+
+#[test]
+fn test_store_command_merges_the_sheet() {
+    let path = std::env::temp_dir().join(format!("aseprite-{}.json", std::process::id()));
+    std::fs::write(&path, ARRAY_SHEET).unwrap();
+    let (base_mint, sprite_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let cli = Cli::parse_from([
+        "sprite-manager",
+        "store",
+        "--base-mint",
+        &base_mint.to_string(),
+        "--sprite-mint",
+        &sprite_mint.to_string(),
+        "--aseprite",
+        path.to_str().unwrap(),
+        "--tag",
+        "knight",
+        "--attribute",
+        "frame_ms=80",
+    ]);
+    std::fs::remove_file(&path).unwrap();
+    let store = match cli.command {
+        Command::Store(store) => store,
+        command => panic!("unexpected command {:?}", command),
+    };
+    let args = store_sprite_args(store).unwrap();
+
+    assert_eq!(args.name, "knight");
+    assert_eq!(
+        args.custom_tags,
+        vec!["knight", "armor", "steel", "grounded"]
+    );
+    // The flag's attribute comes first and replaces the sheet's.
+    assert_eq!(
+        args.attributes[0],
+        ("frame_ms".to_string(), AttributeValue::Integer(80))
+    );
+    assert_eq!(args.attributes.len(), 7);
+
+    let missing_name = Cli::try_parse_from([
+        "sprite-manager",
+        "store",
+        "--base-mint",
+        &base_mint.to_string(),
+        "--sprite-mint",
+        &sprite_mint.to_string(),
+    ]);
+    assert!(missing_name.is_err());
+}
+
+#[test]
+fn test_merged_args_are_checked_against_limits() {
+    let path = std::env::temp_dir().join(format!("aseprite-{}.json", Pubkey::new_unique()));
+    std::fs::write(&path, ARRAY_SHEET).unwrap();
+    let (base_mint, sprite_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let parse = |attributes: usize| {
+        let mut argv = vec![
+            "sprite-manager".to_string(),
+            "store".to_string(),
+            "--base-mint".to_string(),
+            base_mint.to_string(),
+            "--sprite-mint".to_string(),
+            sprite_mint.to_string(),
+            "--aseprite".to_string(),
+            path.to_str().unwrap().to_string(),
+        ];
+        for index in 0..attributes {
+            argv.push("--attribute".to_string());
+            argv.push(format!("extra{}=1", index));
+        }
+        match Cli::parse_from(argv).command {
+            Command::Store(store) => store_sprite_args(store).unwrap(),
+            command => panic!("unexpected command {:?}", command),
+        }
+    };
+    let limits = ConfigLimits::default();
+
+    // The sheet brings seven attributes, so nine flags fill the sprite and a tenth overflows it.
+    let args = parse(9);
+    assert!(check_limits(&args, &limits).is_ok());
+    assert!(matches!(
+        check_limits(&parse(10), &limits),
+        Err(CliError::LimitExceeded(message)) if message.starts_with("17 attributes")
+    ));
+    std::fs::remove_file(&path).unwrap();
+
+    let long_value = StoreSpriteArgs {
+        attributes: vec![("notes".to_string(), string(&"a".repeat(65)))],
+        ..args.clone()
+    };
+    assert!(check_limits(&long_value, &limits).is_err());
+    let long_tag = StoreSpriteArgs {
+        custom_tags: vec!["a".repeat(33)],
+        ..args.clone()
+    };
+    assert!(check_limits(&long_tag, &limits).is_err());
+    let many_tags = StoreSpriteArgs {
+        custom_tags: (0..17).map(|index| format!("tag{}", index)).collect(),
+        ..args.clone()
+    };
+    assert!(check_limits(&many_tags, &limits).is_err());
+    assert!(check_limits(
+        &many_tags,
+        &ConfigLimits {
+            max_custom_tags: 17,
+            ..limits
+        }
+    )
+    .is_ok());
+}
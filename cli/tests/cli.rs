@@ -0,0 +1,172 @@
+use clap::CommandFactory;
+use sprite_manager_cli::Cli;
+#[cfg(feature = "test-bpf")]
+use {
+    clap::Parser,
+    solana_program_test::*,
+    solana_sdk::{signature::Keypair, signer::Signer},
+    sprite_manager::state::{AttributeValue, EquipmentSlot, StyleTags},
+    sprite_manager_cli::{run, CliError, Output, OutputFormat},
+    sprite_manager_client::SpriteManagerClient,
+    sprite_manager_test_utils::*,
+};
+
+#[cfg(feature = "test-bpf")]
+async fn run_cli(
+    client: &mut SpriteManagerClient<BanksClient>,
+    signer: &Keypair,
+    args: &[&str],
+) -> Result<Output, CliError> {
+    let cli = Cli::parse_from(std::iter::once("sprite-manager").chain(args.iter().copied()));
+    run(client, Some(signer), cli.command).await
+}
+
+#[test]
+fn test_cli_definition() {
+    Cli::command().debug_assert();
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_cli_commands() {
+    let mut context = program_test().start_with_context().await;
+
+    let (base, _, _) = create_nft(&mut context, false, None).await;
+    let (hat, _, _) = create_nft(&mut context, false, None).await;
+    let base_mint = base.mint.pubkey().to_string();
+    let hat_mint = hat.mint.pubkey().to_string();
+
+    let mut client = SpriteManagerClient::new(context.banks_client.clone());
+    let payer = context.payer;
+
+    let err = run_cli(&mut client, &payer, &["list", "--base-mint", &base_mint])
+        .await
+        .expect_err("listing before creation should fail");
+    assert!(matches!(err, CliError::SpriteAccountNotFound(mint) if mint == base.mint.pubkey()));
+
+    run_cli(
+        &mut client,
+        &payer,
+        &[
+            "create",
+            "--base-mint",
+            &base_mint,
+            "--base-token-account",
+            &base.token.pubkey().to_string(),
+        ],
+    )
+    .await
+    .expect("create should succeed");
+
+    run_cli(
+        &mut client,
+        &payer,
+        &[
+            "store",
+            "--base-mint",
+            &base_mint,
+            "--sprite-mint",
+            &hat_mint,
+            "--sprite-token-account",
+            &hat.token.pubkey().to_string(),
+            "--name",
+            "Straw hat",
+            "--slot",
+            "hat",
+            "--z-order",
+            "3",
+            "--style",
+            "pixel",
+            "--attribute",
+            "rarity=epic",
+            "--attribute",
+            "season=3",
+            "--license",
+            "cc0",
+        ],
+    )
+    .await
+    .expect("store should succeed");
+
+    let sprite = match run_cli(
+        &mut client,
+        &payer,
+        &[
+            "show",
+            "--base-mint",
+            &base_mint,
+            "--sprite-mint",
+            &hat_mint,
+        ],
+    )
+    .await
+    .expect("show should succeed")
+    {
+        Output::Sprite(sprite) => sprite,
+        output => panic!("unexpected output {:?}", output),
+    };
+    assert_eq!(sprite.name, "Straw hat");
+    assert_eq!(sprite.slot, EquipmentSlot::Hat);
+    assert_eq!(sprite.z_order, 3);
+    assert_eq!(sprite.style_tags, vec![StyleTags::Pixel]);
+    assert_eq!(
+        sprite.attribute("season"),
+        Some(&AttributeValue::Integer(3))
+    );
+    assert!(!sprite.equipped);
+
+    run_cli(
+        &mut client,
+        &payer,
+        &[
+            "update",
+            "--base-mint",
+            &base_mint,
+            "--sprite-mint",
+            &hat_mint,
+            "--base-token-account",
+            &base.token.pubkey().to_string(),
+            "--equip",
+        ],
+    )
+    .await
+    .expect("equipping should succeed");
+
+    let output = run_cli(
+        &mut client,
+        &payer,
+        &["list", "--base-mint", &base_mint, "--equipped"],
+    )
+    .await
+    .expect("list should succeed");
+    match &output {
+        Output::Sprites(sprites) => {
+            assert_eq!(sprites.len(), 1);
+            assert_eq!(sprites[0].mint, hat.mint.pubkey());
+            assert!(sprites[0].equipped);
+        }
+        output => panic!("unexpected output {:?}", output),
+    }
+    let json = output.to_json();
+    assert_eq!(json[0]["mint"], hat_mint);
+    assert_eq!(json[0]["slot"], "Hat");
+    assert_eq!(
+        json[0]["attributes"][0],
+        serde_json::json!(["rarity", "epic"])
+    );
+    assert_eq!(json[0]["attributes"][1], serde_json::json!(["season", 3]));
+    assert_eq!(json[0]["license"], "Cc0");
+    assert!(output.render(OutputFormat::Display).contains("Straw hat"));
+
+    let output = run_cli(&mut client, &payer, &["show", "--base-mint", &base_mint])
+        .await
+        .expect("show should succeed");
+    match &output {
+        Output::Account { account, .. } => {
+            assert_eq!(account.base_mint, base.mint.pubkey());
+            assert_eq!(account.sprites.len(), 1);
+        }
+        output => panic!("unexpected output {:?}", output),
+    }
+    assert_eq!(output.to_json()["account"]["base_mint"], base_mint);
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use solana_banks_client::BanksClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account, hash::Hash, pubkey::Pubkey, signature::Signature, transaction::Transaction,
+};
+
+use crate::error::Result;
+
+/// The cluster access the client needs. Implemented for `RpcClient`, and for `BanksClient` so the
+/// client can be driven from `solana-program-test`.
+#[async_trait]
+pub trait Connection {
+    async fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>>;
+
+    async fn get_latest_blockhash(&mut self) -> Result<Hash>;
+
+    /// Sends the transaction and waits for it to be confirmed.
+    async fn send_transaction(&mut self, transaction: &Transaction) -> Result<Signature>;
+}
+
+#[async_trait]
+impl Connection for RpcClient {
+    async fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>> {
+        Ok(self
+            .get_account_with_commitment(address, self.commitment())
+            .await?
+            .value)
+    }
+
+    async fn get_latest_blockhash(&mut self) -> Result<Hash> {
+        Ok(RpcClient::get_latest_blockhash(self).await?)
+    }
+
+    async fn send_transaction(&mut self, transaction: &Transaction) -> Result<Signature> {
+        Ok(self.send_and_confirm_transaction(transaction).await?)
+    }
+}
+
+#[async_trait]
+impl Connection for BanksClient {
+    async fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>> {
+        Ok(BanksClient::get_account(self, *address).await?)
+    }
+
+    async fn get_latest_blockhash(&mut self) -> Result<Hash> {
+        Ok(BanksClient::get_latest_blockhash(self).await?)
+    }
+
+    async fn send_transaction(&mut self, transaction: &Transaction) -> Result<Signature> {
+        self.process_transaction(transaction.clone()).await?;
+        Ok(transaction.signatures[0])
+    }
+}
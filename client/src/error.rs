@@ -0,0 +1,29 @@
+use solana_banks_client::BanksClientError;
+use solana_program::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error(transparent)]
+    Rpc(Box<solana_client::client_error::ClientError>),
+
+    #[error(transparent)]
+    Banks(Box<BanksClientError>),
+
+    #[error("Failed to deserialize account {0}")]
+    Deserialize(Pubkey),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(e))
+    }
+}
+
+impl From<BanksClientError> for ClientError {
+    fn from(e: BanksClientError) -> Self {
+        ClientError::Banks(Box::new(e))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
@@ -0,0 +1,54 @@
+//! Byte offsets of the fixed-size fields at the start of the program's accounts, and
+//! `getProgramAccounts` filters built on them.
+//!
+//! `SpriteAccount` is laid out as the key byte, the base mint, the created and updated timestamps
+//! and then the variable-length `families` and `sprites` vectors. Nothing after the timestamps
+//! sits at a fixed offset, so there is no memcmp filter by stored or active sprite: fetch the
+//! accounts with [`all_sprite_accounts`] and match `SpriteAccount::families` after decoding, or
+//! use the indexer.
+
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+use sprite_manager::state::Key;
+
+/// The account type discriminator, the first byte of every program account.
+pub const KEY_OFFSET: usize = 0;
+
+pub const SPRITE_ACCOUNT_BASE_MINT_OFFSET: usize = KEY_OFFSET + 1;
+pub const SPRITE_ACCOUNT_CREATED_AT_OFFSET: usize = SPRITE_ACCOUNT_BASE_MINT_OFFSET + 32;
+pub const SPRITE_ACCOUNT_UPDATED_AT_OFFSET: usize =
+    SPRITE_ACCOUNT_CREATED_AT_OFFSET + TIMESTAMP_SIZE;
+/// The `u32` length prefix of the `families` vector, where the variable-length data begins.
+pub const SPRITE_ACCOUNT_FAMILIES_OFFSET: usize = SPRITE_ACCOUNT_UPDATED_AT_OFFSET + TIMESTAMP_SIZE;
+
+pub const PROGRAM_CONFIG_ADMIN_OFFSET: usize = KEY_OFFSET + 1;
+
+/// A `Timestamp` is an `i64` unix timestamp followed by a `u64` slot.
+const TIMESTAMP_SIZE: usize = 16;
+
+/// Matches accounts of the given type.
+pub fn key(key: Key) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(KEY_OFFSET, &[key as u8]))
+}
+
+/// Matches every sprite account in the current layout.
+pub fn all_sprite_accounts() -> Vec<RpcFilterType> {
+    vec![key(Key::SpriteAccount)]
+}
+
+/// Matches the sprite accounts still in the original layout, which `MigrateSpriteAccount` has not
+/// rewritten yet. Their base mint sits at the same offset.
+pub fn unmigrated_sprite_accounts() -> Vec<RpcFilterType> {
+    vec![key(Key::SpriteAccountV1)]
+}
+
+/// Matches the sprite account of `base_mint`.
+pub fn sprite_accounts_by_base_mint(base_mint: &Pubkey) -> Vec<RpcFilterType> {
+    vec![
+        key(Key::SpriteAccount),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            SPRITE_ACCOUNT_BASE_MINT_OFFSET,
+            base_mint.as_ref(),
+        )),
+    ]
+}
@@ -0,0 +1,147 @@
+//! Async Rust client for the sprite manager program.
+//!
+//! [`SpriteManagerClient`] fetches and decodes program accounts and builds and sends transactions,
+//! deriving every PDA, escrow and token account address from the base and sprite mints.
+
+mod connection;
+mod error;
+pub mod filters;
+
+pub use connection::Connection;
+pub use error::{ClientError, Result};
+pub use sprite_manager;
+
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+use sprite_manager::{
+    builders::{CreateSpriteAccountBuilder, StoreSpriteBuilder},
+    instruction::StoreSpriteArgs,
+    pda::{find_config_address, find_sprite_address},
+    state::{FeeSchedule, ProgramConfig, SolanaAccount, SpriteAccount},
+};
+
+pub struct SpriteManagerClient<C> {
+    connection: C,
+}
+
+impl<C: Connection + Send> SpriteManagerClient<C> {
+    pub fn new(connection: C) -> Self {
+        Self { connection }
+    }
+
+    pub fn connection(&mut self) -> &mut C {
+        &mut self.connection
+    }
+
+    /// Fetches the sprite account of a base token, `None` if it has not been created.
+    pub async fn get_sprite_account(
+        &mut self,
+        base_mint: &Pubkey,
+    ) -> Result<Option<SpriteAccount>> {
+        let (address, _) = find_sprite_address(base_mint);
+        self.get_decoded(&address).await
+    }
+
+    /// Fetches the program config, `None` if it has not been initialized.
+    pub async fn get_program_config(&mut self) -> Result<Option<ProgramConfig>> {
+        let (address, _) = find_config_address();
+        self.get_decoded(&address).await
+    }
+
+    /// Builds a `CreateSpriteAccount` instruction for the base token held in `base_token_account`.
+    pub fn create_sprite_account_instruction(
+        owner: &Pubkey,
+        base_mint: &Pubkey,
+        base_token_account: &Pubkey,
+    ) -> Instruction {
+        CreateSpriteAccountBuilder::new(*base_mint, *owner)
+            .base_token_account(*base_token_account)
+            .instruction()
+    }
+
+    pub async fn create_sprite_account(
+        &mut self,
+        owner: &Keypair,
+        base_mint: &Pubkey,
+        base_token_account: &Pubkey,
+    ) -> Result<Signature> {
+        let instruction =
+            Self::create_sprite_account_instruction(&owner.pubkey(), base_mint, base_token_account);
+        self.send(&[instruction], owner).await
+    }
+
+    /// Builds a `StoreSprite` instruction that moves the sprite token out of `sprite_token_account`
+    /// into the base token's escrow. The fee accounts are resolved from the program config, with
+    /// token fees paid between the owner's and the treasury's associated token accounts.
+    pub async fn store_sprite_instruction(
+        &mut self,
+        owner: &Pubkey,
+        base_mint: &Pubkey,
+        sprite_mint: &Pubkey,
+        sprite_token_account: &Pubkey,
+        args: StoreSpriteArgs,
+    ) -> Result<Instruction> {
+        let fees = self
+            .get_program_config()
+            .await?
+            .map(|config| config.fees)
+            .unwrap_or_else(FeeSchedule::default);
+
+        Ok(
+            StoreSpriteBuilder::new(*base_mint, *sprite_mint, *owner, args)
+                .sprite_token_account(*sprite_token_account)
+                .fees(&fees)
+                .instruction(),
+        )
+    }
+
+    pub async fn store_sprite(
+        &mut self,
+        owner: &Keypair,
+        base_mint: &Pubkey,
+        sprite_mint: &Pubkey,
+        sprite_token_account: &Pubkey,
+        args: StoreSpriteArgs,
+    ) -> Result<Signature> {
+        let instruction = self
+            .store_sprite_instruction(
+                &owner.pubkey(),
+                base_mint,
+                sprite_mint,
+                sprite_token_account,
+                args,
+            )
+            .await?;
+        self.send(&[instruction], owner).await
+    }
+
+    /// Signs `instructions` with `payer` as the fee payer and sends them in one transaction.
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+    ) -> Result<Signature> {
+        let blockhash = self.connection.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            blockhash,
+        );
+        self.connection.send_transaction(&transaction).await
+    }
+
+    async fn get_decoded<T: SolanaAccount>(&mut self, address: &Pubkey) -> Result<Option<T>> {
+        match self.connection.get_account(address).await? {
+            Some(account) if !account.data.is_empty() => T::safe_deserialize(&account.data)
+                .map(Some)
+                .map_err(|_| ClientError::Deserialize(*address)),
+            _ => Ok(None),
+        }
+    }
+}
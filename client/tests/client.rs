@@ -0,0 +1,58 @@
+#![cfg(feature = "test-bpf")]
+
+use solana_program_test::*;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use sprite_manager::instruction::StoreSpriteArgs;
+use sprite_manager_client::SpriteManagerClient;
+use sprite_manager_test_utils::*;
+
+#[tokio::test]
+async fn test_create_store_and_fetch() {
+    let mut context = program_test().start_with_context().await;
+
+    let (base, _, _) = create_nft(&mut context, false, None).await;
+    let (sprite, _, _) = create_nft(&mut context, false, None).await;
+
+    let mut client = SpriteManagerClient::new(context.banks_client.clone());
+    assert!(client
+        .get_sprite_account(&Pubkey::new_unique())
+        .await
+        .unwrap()
+        .is_none());
+
+    client
+        .create_sprite_account(&context.payer, &base.mint.pubkey(), &base.token.pubkey())
+        .await
+        .expect("creating the sprite account should succeed");
+
+    let sprite_account = client
+        .get_sprite_account(&base.mint.pubkey())
+        .await
+        .unwrap()
+        .expect("sprite account should exist");
+    assert_eq!(sprite_account.base_mint, base.mint.pubkey());
+    assert!(sprite_account.sprites.is_empty());
+
+    client
+        .store_sprite(
+            &context.payer,
+            &base.mint.pubkey(),
+            &sprite.mint.pubkey(),
+            &sprite.token.pubkey(),
+            StoreSpriteArgs {
+                name: "sprite".to_string(),
+                description: "a sprite".to_string(),
+                ..StoreSpriteArgs::default()
+            },
+        )
+        .await
+        .expect("storing the sprite should succeed");
+
+    let sprite_account = client
+        .get_sprite_account(&base.mint.pubkey())
+        .await
+        .unwrap()
+        .expect("sprite account should exist");
+    assert!(sprite_account.find_sprite(&sprite.mint.pubkey()).is_some());
+    assert!(client.get_program_config().await.unwrap().is_none());
+}
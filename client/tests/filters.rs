@@ -0,0 +1,129 @@
+use borsh::BorshSerialize;
+use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::{account::AccountSharedData, pubkey::Pubkey};
+use sprite_manager::state::{
+    ConfigLimits, FeeSchedule, Key, ProgramConfig, Sprite, SpriteAccount, SpriteAccountV1,
+    SpriteFamily, Timestamp,
+};
+use sprite_manager_client::filters::*;
+
+fn account_data(data: Vec<u8>) -> AccountSharedData {
+    let mut account = AccountSharedData::new(1, data.len(), &sprite_manager::id());
+    account.set_data(data);
+    account
+}
+
+fn sprite_account(base_mint: Pubkey) -> SpriteAccount {
+    let sprite = Sprite {
+        mint: Pubkey::new_unique(),
+        ..Sprite::default()
+    };
+    SpriteAccount {
+        key: Key::SpriteAccount,
+        base_mint,
+        created_at: Timestamp {
+            unix_timestamp: -1,
+            slot: 7,
+        },
+        updated_at: Timestamp {
+            unix_timestamp: 1_666_000_000,
+            slot: u64::MAX,
+        },
+        families: vec![SpriteFamily {
+            id: Pubkey::new_unique(),
+            active_variant: sprite.mint,
+            variant_count: 1,
+        }],
+        sprites: vec![sprite],
+    }
+}
+
+#[test]
+fn test_sprite_account_offsets() {
+    let account = sprite_account(Pubkey::new_unique());
+    let data = account.try_to_vec().unwrap();
+
+    assert_eq!(data[KEY_OFFSET], Key::SpriteAccount as u8);
+    assert_eq!(
+        &data[SPRITE_ACCOUNT_BASE_MINT_OFFSET..SPRITE_ACCOUNT_CREATED_AT_OFFSET],
+        account.base_mint.as_ref()
+    );
+    assert_eq!(
+        &data[SPRITE_ACCOUNT_CREATED_AT_OFFSET..SPRITE_ACCOUNT_UPDATED_AT_OFFSET],
+        account.created_at.try_to_vec().unwrap()
+    );
+    assert_eq!(
+        &data[SPRITE_ACCOUNT_UPDATED_AT_OFFSET..SPRITE_ACCOUNT_FAMILIES_OFFSET],
+        account.updated_at.try_to_vec().unwrap()
+    );
+    assert_eq!(
+        &data[SPRITE_ACCOUNT_FAMILIES_OFFSET..],
+        [
+            account.families.try_to_vec().unwrap(),
+            account.sprites.try_to_vec().unwrap()
+        ]
+        .concat()
+    );
+}
+
+#[test]
+fn test_program_config_offsets() {
+    let config = ProgramConfig {
+        key: Key::ProgramConfig,
+        admin: Pubkey::new_unique(),
+        pending_admin: None,
+        paused: false,
+        limits: ConfigLimits::default(),
+        fees: FeeSchedule::default(),
+    };
+    let data = config.try_to_vec().unwrap();
+
+    assert_eq!(data[KEY_OFFSET], Key::ProgramConfig as u8);
+    assert_eq!(
+        &data[PROGRAM_CONFIG_ADMIN_OFFSET..PROGRAM_CONFIG_ADMIN_OFFSET + 32],
+        config.admin.as_ref()
+    );
+}
+
+#[test]
+fn test_filters() {
+    let base_mint = Pubkey::new_unique();
+    let owned = account_data(sprite_account(base_mint).try_to_vec().unwrap());
+    let other = account_data(sprite_account(Pubkey::new_unique()).try_to_vec().unwrap());
+    let config = account_data(
+        ProgramConfig {
+            key: Key::ProgramConfig,
+            ..ProgramConfig::default()
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+    let unmigrated = account_data(
+        SpriteAccountV1 {
+            key: Key::SpriteAccountV1,
+            base_mint,
+            sprites: vec![],
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+
+    let matches = |filters: &[RpcFilterType], account: &AccountSharedData| {
+        filters.iter().all(|filter| filter.allows(account))
+    };
+
+    let all = all_sprite_accounts();
+    assert!(matches(&all, &owned));
+    assert!(matches(&all, &other));
+    assert!(!matches(&all, &config));
+    assert!(!matches(&all, &unmigrated));
+
+    let original = unmigrated_sprite_accounts();
+    assert!(matches(&original, &unmigrated));
+    assert!(!matches(&original, &owned));
+
+    let by_base_mint = sprite_accounts_by_base_mint(&base_mint);
+    assert!(matches(&by_base_mint, &owned));
+    assert!(!matches(&by_base_mint, &other));
+    assert!(!matches(&by_base_mint, &config));
+}
@@ -0,0 +1,38 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::sink::{EventSink, FileSink};
+
+/// The plugin's JSON config file. The validator reads `libpath` from the same file.
+#[derive(Debug, Deserialize)]
+pub struct PluginConfig {
+    pub sink: SinkConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SinkConfig {
+    File(PathBuf),
+    #[cfg(unix)]
+    UnixSocket(PathBuf),
+}
+
+impl PluginConfig {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+impl SinkConfig {
+    pub fn open(&self) -> io::Result<Box<dyn EventSink>> {
+        Ok(match self {
+            SinkConfig::File(path) => Box::new(FileSink::open(path)?),
+            #[cfg(unix)]
+            SinkConfig::UnixSocket(path) => Box::new(crate::sink::UnixSocketSink::connect(path)?),
+        })
+    }
+}
@@ -0,0 +1,139 @@
+use borsh::BorshSerialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use sprite_manager::state::{Sprite, SpriteAccount};
+
+/// A change to one sprite account, as published to the plugin's sink.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    pub slot: u64,
+    pub write_version: u64,
+    /// The first signature of the transaction that made the change, when the validator reports it.
+    pub signature: Option<String>,
+    #[serde(with = "sprite_manager::serde_pubkey")]
+    pub sprite_account: Pubkey,
+    #[serde(with = "sprite_manager::serde_pubkey")]
+    pub base_mint: Pubkey,
+    pub change: SpriteChange,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum SpriteChange {
+    SpriteAccountCreated,
+    SpriteAccountClosed,
+    SpriteStored {
+        sprite: Sprite,
+    },
+    SpriteRemoved {
+        #[serde(with = "sprite_manager::serde_pubkey")]
+        sprite_mint: Pubkey,
+    },
+    SpriteEquipped {
+        #[serde(with = "sprite_manager::serde_pubkey")]
+        sprite_mint: Pubkey,
+    },
+    SpriteUnequipped {
+        #[serde(with = "sprite_manager::serde_pubkey")]
+        sprite_mint: Pubkey,
+    },
+    /// Anything else about a stored sprite changed, such as its palette variant.
+    SpriteUpdated {
+        sprite: Sprite,
+    },
+    ActiveVariantChanged {
+        #[serde(with = "sprite_manager::serde_pubkey")]
+        family: Pubkey,
+        #[serde(with = "sprite_manager::serde_pubkey::option")]
+        previous_variant: Option<Pubkey>,
+        #[serde(with = "sprite_manager::serde_pubkey")]
+        active_variant: Pubkey,
+    },
+}
+
+/// The changes that turn `previous` into `current`, `None` meaning the account does not exist.
+/// A new account is reported as created, followed by its sprites as if they had just been stored.
+pub fn diff(
+    previous: Option<&SpriteAccount>,
+    current: Option<&SpriteAccount>,
+) -> Vec<SpriteChange> {
+    let current = match current {
+        Some(current) => current,
+        None if previous.is_some() => return vec![SpriteChange::SpriteAccountClosed],
+        None => return vec![],
+    };
+
+    let mut changes = vec![];
+    let empty = SpriteAccount::default();
+    let previous = previous.unwrap_or_else(|| {
+        changes.push(SpriteChange::SpriteAccountCreated);
+        &empty
+    });
+
+    for sprite in &current.sprites {
+        match previous.find_sprite(&sprite.mint) {
+            None => {
+                changes.push(SpriteChange::SpriteStored {
+                    sprite: sprite.clone(),
+                });
+                if sprite.equipped {
+                    changes.push(SpriteChange::SpriteEquipped {
+                        sprite_mint: sprite.mint,
+                    });
+                }
+            }
+            Some(before) => {
+                if before.equipped != sprite.equipped {
+                    changes.push(if sprite.equipped {
+                        SpriteChange::SpriteEquipped {
+                            sprite_mint: sprite.mint,
+                        }
+                    } else {
+                        SpriteChange::SpriteUnequipped {
+                            sprite_mint: sprite.mint,
+                        }
+                    });
+                }
+                if changed_besides_equipping(before, sprite) {
+                    changes.push(SpriteChange::SpriteUpdated {
+                        sprite: sprite.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    for sprite in &previous.sprites {
+        if current.find_sprite(&sprite.mint).is_none() {
+            changes.push(SpriteChange::SpriteRemoved {
+                sprite_mint: sprite.mint,
+            });
+        }
+    }
+
+    for family in &current.families {
+        let previous_variant = previous
+            .find_family(&family.id)
+            .map(|before| before.active_variant);
+        if previous_variant != Some(family.active_variant) {
+            changes.push(SpriteChange::ActiveVariantChanged {
+                family: family.id,
+                previous_variant,
+                active_variant: family.active_variant,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Whether the sprite changed in more than its `equipped` flag and the `updated_at` stamp that
+/// equipping bumps.
+fn changed_besides_equipping(before: &Sprite, after: &Sprite) -> bool {
+    let before = Sprite {
+        equipped: after.equipped,
+        updated_at: after.updated_at,
+        ..before.clone()
+    };
+    before.try_to_vec().ok() != after.try_to_vec().ok()
+}
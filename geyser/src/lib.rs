@@ -0,0 +1,31 @@
+//! Geyser plugin streaming changes to sprite manager accounts.
+//!
+//! The validator hands every account write to the plugin. Writes to accounts owned by the sprite
+//! manager program are decoded, diffed against the last known state of the account by a
+//! [`SpriteAccountTracker`] and published as [`ChangeEvent`]s to an [`EventSink`].
+//!
+//! Build the loadable plugin with the `plugin` feature and point the validator at a config file
+//! such as
+//!
+//! ```json
+//! {
+//!     "libpath": "/path/to/libsprite_manager_geyser.so",
+//!     "sink": { "unix_socket": "/tmp/sprite-changes.sock" }
+//! }
+//! ```
+//!
+//! where the sink is either `{ "file": "<path>" }` or `{ "unix_socket": "<path>" }`.
+
+pub mod config;
+pub mod event;
+#[cfg(feature = "plugin")]
+mod plugin;
+pub mod sink;
+mod tracker;
+
+pub use config::{PluginConfig, SinkConfig};
+pub use event::{diff, ChangeEvent, SpriteChange};
+#[cfg(feature = "plugin")]
+pub use plugin::SpriteManagerPlugin;
+pub use sink::EventSink;
+pub use tracker::{AccountUpdate, SpriteAccountTracker};
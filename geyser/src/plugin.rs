@@ -0,0 +1,100 @@
+use log::info;
+use solana_geyser_plugin_interface::geyser_plugin_interface::{
+    GeyserPlugin, GeyserPluginError, ReplicaAccountInfoVersions, Result,
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    config::PluginConfig,
+    tracker::{AccountUpdate, SpriteAccountTracker},
+};
+
+/// The Geyser plugin, a [`SpriteAccountTracker`] publishing to the sink from the config file.
+#[derive(Debug, Default)]
+pub struct SpriteManagerPlugin {
+    tracker: Option<SpriteAccountTracker>,
+}
+
+impl GeyserPlugin for SpriteManagerPlugin {
+    fn name(&self) -> &'static str {
+        "sprite-manager-geyser"
+    }
+
+    fn on_load(&mut self, config_file: &str) -> Result<()> {
+        let config = PluginConfig::load(config_file).map_err(|err| {
+            GeyserPluginError::ConfigFileReadError {
+                msg: err.to_string(),
+            }
+        })?;
+        info!("Publishing sprite account changes to {:?}", config.sink);
+        self.tracker = Some(SpriteAccountTracker::new(config.sink.open()?));
+        Ok(())
+    }
+
+    fn update_account(
+        &mut self,
+        account: ReplicaAccountInfoVersions,
+        slot: u64,
+        is_startup: bool,
+    ) -> Result<()> {
+        let tracker = match &mut self.tracker {
+            Some(tracker) => tracker,
+            None => return Ok(()),
+        };
+        let (pubkey, owner, lamports, data, write_version, signature) = match account {
+            ReplicaAccountInfoVersions::V0_0_1(info) => (
+                info.pubkey,
+                info.owner,
+                info.lamports,
+                info.data,
+                info.write_version,
+                None,
+            ),
+            ReplicaAccountInfoVersions::V0_0_2(info) => (
+                info.pubkey,
+                info.owner,
+                info.lamports,
+                info.data,
+                info.write_version,
+                info.txn_signature.map(|signature| signature.to_string()),
+            ),
+        };
+        let to_pubkey = |bytes: &[u8]| {
+            <[u8; 32]>::try_from(bytes)
+                .map(Pubkey::new_from_array)
+                .map_err(|_| GeyserPluginError::AccountsUpdateError {
+                    msg: format!("Invalid pubkey {:?}", bytes),
+                })
+        };
+        let update = AccountUpdate {
+            pubkey: to_pubkey(pubkey)?,
+            owner: to_pubkey(owner)?,
+            lamports,
+            data,
+            write_version,
+            signature,
+        };
+
+        tracker.update(&update, slot, is_startup).map_err(|err| {
+            GeyserPluginError::AccountsUpdateError {
+                msg: format!("Failed to publish sprite account changes: {}", err),
+            }
+        })
+    }
+
+    fn account_data_notifications_enabled(&self) -> bool {
+        true
+    }
+
+    fn transaction_notifications_enabled(&self) -> bool {
+        false
+    }
+}
+
+/// Entry point the validator calls after loading the library.
+#[no_mangle]
+#[allow(improper_ctypes_definitions)]
+pub extern "C" fn _create_plugin() -> *mut dyn GeyserPlugin {
+    let plugin: Box<dyn GeyserPlugin> = Box::new(SpriteManagerPlugin::default());
+    Box::into_raw(plugin)
+}
@@ -0,0 +1,163 @@
+//! Destinations for change events. The file and socket sinks write one JSON event per line.
+
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::{self, Write},
+    path::Path,
+    sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use log::{error, warn};
+
+use crate::event::ChangeEvent;
+
+/// Events a [`QueuedWriter`] holds while its writer catches up.
+pub const QUEUE_CAPACITY: usize = 1024;
+
+/// How long the socket sink waits on a stalled reader before giving up on it.
+#[cfg(unix)]
+pub const SOCKET_WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub trait EventSink: Send + Sync + fmt::Debug {
+    fn publish(&mut self, event: &ChangeEvent) -> io::Result<()>;
+}
+
+fn json_line(event: &ChangeEvent) -> io::Result<Vec<u8>> {
+    let mut line = serde_json::to_vec(event)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// Writes event lines from a background thread, so publishing never waits on I/O in the
+/// validator's callback. Events published while `capacity` lines are already queued are dropped
+/// and logged. Once a write fails, publishing fails too.
+///
+/// Dropping the writer waits for the queued lines to be written.
+#[derive(Debug)]
+pub struct QueuedWriter {
+    sender: Option<SyncSender<Vec<u8>>>,
+    thread: Option<JoinHandle<()>>,
+    dropped: u64,
+}
+
+impl QueuedWriter {
+    pub fn new<W: Write + Send + 'static>(mut writer: W, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<Vec<u8>>(capacity);
+        let thread = thread::spawn(move || {
+            for line in receiver {
+                if let Err(err) = writer.write_all(&line) {
+                    error!("Failed to write sprite account change: {}", err);
+                    return;
+                }
+            }
+        });
+        Self {
+            sender: Some(sender),
+            thread: Some(thread),
+            dropped: 0,
+        }
+    }
+}
+
+impl EventSink for QueuedWriter {
+    fn publish(&mut self, event: &ChangeEvent) -> io::Result<()> {
+        let sender = self.sender.as_ref().expect("sender is only taken on drop");
+        match sender.try_send(json_line(event)?) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                self.dropped += 1;
+                // Logs the 1st, 2nd, 4th, 8th... drop so a stalled reader does not flood the log.
+                if self.dropped.is_power_of_two() {
+                    warn!(
+                        "Event queue is full, {} sprite account changes dropped",
+                        self.dropped
+                    );
+                }
+                Ok(())
+            }
+            Err(TrySendError::Disconnected(_)) => Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "event writer stopped",
+            )),
+        }
+    }
+}
+
+impl Drop for QueuedWriter {
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Appends events to a file.
+#[derive(Debug)]
+pub struct FileSink {
+    writer: QueuedWriter,
+}
+
+impl FileSink {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: QueuedWriter::new(file, QUEUE_CAPACITY),
+        })
+    }
+}
+
+impl EventSink for FileSink {
+    fn publish(&mut self, event: &ChangeEvent) -> io::Result<()> {
+        self.writer.publish(event)
+    }
+}
+
+/// Streams events to a process listening on a Unix socket.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct UnixSocketSink {
+    writer: QueuedWriter,
+}
+
+#[cfg(unix)]
+impl UnixSocketSink {
+    pub fn connect(path: impl AsRef<Path>) -> io::Result<Self> {
+        let stream = std::os::unix::net::UnixStream::connect(path)?;
+        stream.set_write_timeout(Some(SOCKET_WRITE_TIMEOUT))?;
+        Ok(Self {
+            writer: QueuedWriter::new(stream, QUEUE_CAPACITY),
+        })
+    }
+}
+
+#[cfg(unix)]
+impl EventSink for UnixSocketSink {
+    fn publish(&mut self, event: &ChangeEvent) -> io::Result<()> {
+        self.writer.publish(event)
+    }
+}
+
+/// Sends events to a receiver in the same process.
+#[derive(Debug)]
+pub struct ChannelSink {
+    sender: Sender<ChangeEvent>,
+}
+
+impl ChannelSink {
+    pub fn new() -> (Self, Receiver<ChangeEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self { sender }, receiver)
+    }
+}
+
+impl EventSink for ChannelSink {
+    fn publish(&mut self, event: &ChangeEvent) -> io::Result<()> {
+        self.sender
+            .send(event.clone())
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "event receiver was dropped"))
+    }
+}
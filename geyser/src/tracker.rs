@@ -0,0 +1,135 @@
+use std::{collections::HashMap, io};
+
+use log::warn;
+use solana_sdk::pubkey::Pubkey;
+use sprite_manager::state::{SolanaAccount, SpriteAccount};
+
+use crate::{
+    event::{diff, ChangeEvent},
+    sink::EventSink,
+};
+
+/// An account write as reported by the validator.
+#[derive(Clone, Debug)]
+pub struct AccountUpdate<'a> {
+    pub pubkey: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: &'a [u8],
+    /// Orders writes to the same account, later writes have higher versions.
+    pub write_version: u64,
+    /// The first signature of the transaction that made the write, if known.
+    pub signature: Option<String>,
+}
+
+impl AccountUpdate<'_> {
+    /// Closed accounts and accounts of other types are treated as gone.
+    fn is_sprite_account(&self) -> bool {
+        self.owner == sprite_manager::id()
+            && self.lamports > 0
+            && SpriteAccount::has_sprite_account_key(self.data)
+    }
+}
+
+struct TrackedAccount {
+    write_version: u64,
+    account: SpriteAccount,
+}
+
+/// Keeps the last known state of every sprite account and publishes the changes each write makes
+/// to it.
+///
+/// Accounts loaded from the snapshot at startup only seed the known state, so the first events
+/// are the changes made after the validator started.
+pub struct SpriteAccountTracker {
+    sink: Box<dyn EventSink>,
+    accounts: HashMap<Pubkey, TrackedAccount>,
+}
+
+impl std::fmt::Debug for SpriteAccountTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpriteAccountTracker")
+            .field("sink", &self.sink)
+            .field("accounts", &self.accounts.len())
+            .finish()
+    }
+}
+
+impl SpriteAccountTracker {
+    pub fn new(sink: Box<dyn EventSink>) -> Self {
+        Self {
+            sink,
+            accounts: HashMap::new(),
+        }
+    }
+
+    /// The last known state of the sprite account at `address`.
+    pub fn sprite_account(&self, address: &Pubkey) -> Option<&SpriteAccount> {
+        self.accounts.get(address).map(|tracked| &tracked.account)
+    }
+
+    /// Applies a write made in `slot`. Writes to other programs' accounts, writes older than the
+    /// known state and data that does not decode are skipped. Fails only if the sink does.
+    pub fn update(
+        &mut self,
+        update: &AccountUpdate,
+        slot: u64,
+        is_startup: bool,
+    ) -> io::Result<()> {
+        let is_sprite_account = update.is_sprite_account();
+        match self.accounts.get(&update.pubkey) {
+            None if !is_sprite_account => return Ok(()),
+            Some(tracked) if update.write_version <= tracked.write_version => return Ok(()),
+            _ => (),
+        }
+
+        let current = if is_sprite_account {
+            match SpriteAccount::safe_deserialize(update.data) {
+                Ok(account) => Some(account),
+                Err(err) => {
+                    warn!(
+                        "Failed to deserialize sprite account {}: {}",
+                        update.pubkey, err
+                    );
+                    return Ok(());
+                }
+            }
+        } else {
+            None
+        };
+
+        let previous = match &current {
+            Some(account) => self.accounts.insert(
+                update.pubkey,
+                TrackedAccount {
+                    write_version: update.write_version,
+                    account: account.clone(),
+                },
+            ),
+            None => self.accounts.remove(&update.pubkey),
+        }
+        .map(|tracked| tracked.account);
+
+        if is_startup {
+            return Ok(());
+        }
+
+        let base_mint = current
+            .as_ref()
+            .or(previous.as_ref())
+            .map(|account| account.base_mint)
+            .unwrap_or_default();
+        for change in diff(previous.as_ref(), current.as_ref()) {
+            self.sink.publish(&ChangeEvent {
+                slot,
+                write_version: update.write_version,
+                signature: update.signature.clone(),
+                sprite_account: update.pubkey,
+                base_mint,
+                change,
+            })?;
+        }
+
+        Ok(())
+    }
+}
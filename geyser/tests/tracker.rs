@@ -0,0 +1,398 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    sync::mpsc::{self, Receiver, Sender},
+};
+
+use borsh::BorshSerialize;
+use solana_sdk::pubkey::Pubkey;
+use sprite_manager::state::{Key, ProgramConfig, Sprite, SpriteAccount, SpriteFamily};
+use sprite_manager_geyser::{
+    sink::{ChannelSink, FileSink, QueuedWriter},
+    AccountUpdate, ChangeEvent, PluginConfig, SinkConfig, SpriteAccountTracker, SpriteChange,
+};
+
+struct Harness {
+    tracker: SpriteAccountTracker,
+    events: Receiver<ChangeEvent>,
+    address: Pubkey,
+    write_version: u64,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let (sink, events) = ChannelSink::new();
+        Self {
+            tracker: SpriteAccountTracker::new(Box::new(sink)),
+            events,
+            address: Pubkey::new_unique(),
+            write_version: 0,
+        }
+    }
+
+    /// Writes `data` to the harness account and returns the published changes.
+    fn write(&mut self, owner: Pubkey, lamports: u64, data: &[u8]) -> Vec<SpriteChange> {
+        self.write_version += 1;
+        let update = AccountUpdate {
+            pubkey: self.address,
+            owner,
+            lamports,
+            data,
+            write_version: self.write_version,
+            signature: None,
+        };
+        self.tracker.update(&update, 1, false).unwrap();
+        self.events.try_iter().map(|event| event.change).collect()
+    }
+
+    fn store(&mut self, account: &SpriteAccount) -> Vec<SpriteChange> {
+        self.write(sprite_manager::id(), 1, &account.try_to_vec().unwrap())
+    }
+}
+
+fn sprite_account(base_mint: Pubkey, sprites: Vec<Sprite>) -> SpriteAccount {
+    SpriteAccount {
+        key: Key::SpriteAccount,
+        base_mint,
+        sprites,
+        ..SpriteAccount::default()
+    }
+}
+
+fn sprite(mint: Pubkey) -> Sprite {
+    Sprite {
+        name: "sprite".to_string(),
+        mint,
+        ..Sprite::default()
+    }
+}
+
+fn names(changes: &[SpriteChange]) -> Vec<&'static str> {
+    changes
+        .iter()
+        .map(|change| match change {
+            SpriteChange::SpriteAccountCreated => "created",
+            SpriteChange::SpriteAccountClosed => "closed",
+            SpriteChange::SpriteStored { .. } => "stored",
+            SpriteChange::SpriteRemoved { .. } => "removed",
+            SpriteChange::SpriteEquipped { .. } => "equipped",
+            SpriteChange::SpriteUnequipped { .. } => "unequipped",
+            SpriteChange::SpriteUpdated { .. } => "updated",
+            SpriteChange::ActiveVariantChanged { .. } => "active_variant",
+        })
+        .collect()
+}
+
+#[test]
+fn test_sprite_lifecycle() {
+    let mut harness = Harness::new();
+    let base_mint = Pubkey::new_unique();
+    let hat = Pubkey::new_unique();
+    let mut account = sprite_account(base_mint, vec![]);
+
+    assert_eq!(names(&harness.store(&account)), ["created"]);
+
+    account.sprites.push(sprite(hat));
+    let changes = harness.store(&account);
+    assert_eq!(names(&changes), ["stored"]);
+    assert!(matches!(&changes[0], SpriteChange::SpriteStored { sprite } if sprite.mint == hat));
+
+    // Equipping also bumps the sprite's timestamp, which is not reported as an update.
+    account.sprites[0].equipped = true;
+    account.sprites[0].updated_at.slot = 5;
+    assert_eq!(names(&harness.store(&account)), ["equipped"]);
+
+    account.sprites[0].palette_variant = Some("night".to_string());
+    assert_eq!(names(&harness.store(&account)), ["updated"]);
+
+    assert!(harness.store(&account).is_empty());
+
+    let family = Pubkey::new_unique();
+    let cape = Pubkey::new_unique();
+    account.sprites.push(sprite(cape));
+    account.families.push(SpriteFamily {
+        id: family,
+        active_variant: hat,
+        variant_count: 2,
+    });
+    assert_eq!(
+        names(&harness.store(&account)),
+        ["stored", "active_variant"]
+    );
+
+    account.families[0].active_variant = cape;
+    account.sprites[0].equipped = false;
+    let changes = harness.store(&account);
+    assert_eq!(names(&changes), ["unequipped", "active_variant"]);
+    assert!(matches!(
+        changes[1],
+        SpriteChange::ActiveVariantChanged {
+            previous_variant: Some(previous),
+            active_variant,
+            ..
+        } if previous == hat && active_variant == cape
+    ));
+
+    account.sprites.remove(0);
+    assert_eq!(names(&harness.store(&account)), ["removed"]);
+
+    assert_eq!(
+        names(&harness.write(solana_sdk::system_program::id(), 0, &[])),
+        ["closed"]
+    );
+    assert!(harness.tracker.sprite_account(&harness.address).is_none());
+}
+
+#[test]
+fn test_ignores_other_accounts() {
+    let mut harness = Harness::new();
+    let account = sprite_account(Pubkey::new_unique(), vec![]);
+    let data = account.try_to_vec().unwrap();
+
+    assert!(harness.write(Pubkey::new_unique(), 1, &data).is_empty());
+    assert!(harness.write(sprite_manager::id(), 1, &[]).is_empty());
+
+    let config = ProgramConfig {
+        key: Key::ProgramConfig,
+        ..ProgramConfig::default()
+    };
+    assert!(harness
+        .write(sprite_manager::id(), 1, &config.try_to_vec().unwrap())
+        .is_empty());
+
+    // Truncated data is skipped without failing the validator.
+    assert!(harness
+        .write(sprite_manager::id(), 1, &data[..10])
+        .is_empty());
+    assert!(harness.tracker.sprite_account(&harness.address).is_none());
+}
+
+#[test]
+fn test_skips_stale_writes() {
+    let mut harness = Harness::new();
+    let hat = Pubkey::new_unique();
+    let empty = sprite_account(Pubkey::new_unique(), vec![]);
+    let stored = sprite_account(empty.base_mint, vec![sprite(hat)]);
+
+    harness.store(&empty);
+    harness.store(&stored);
+
+    let stale = AccountUpdate {
+        pubkey: harness.address,
+        owner: sprite_manager::id(),
+        lamports: 1,
+        data: &empty.try_to_vec().unwrap(),
+        write_version: 1,
+        signature: None,
+    };
+    harness.tracker.update(&stale, 1, false).unwrap();
+
+    assert!(harness.events.try_iter().next().is_none());
+    assert!(harness
+        .tracker
+        .sprite_account(&harness.address)
+        .unwrap()
+        .find_sprite(&hat)
+        .is_some());
+}
+
+#[test]
+fn test_startup_seeds_state() {
+    let mut harness = Harness::new();
+    let hat = Pubkey::new_unique();
+    let mut account = sprite_account(Pubkey::new_unique(), vec![sprite(hat)]);
+    let data = account.try_to_vec().unwrap();
+
+    let update = AccountUpdate {
+        pubkey: harness.address,
+        owner: sprite_manager::id(),
+        lamports: 1,
+        data: &data,
+        write_version: 1,
+        signature: None,
+    };
+    harness.tracker.update(&update, 1, true).unwrap();
+    harness.write_version = 1;
+    assert!(harness.events.try_iter().next().is_none());
+
+    account.sprites[0].equipped = true;
+    assert_eq!(names(&harness.store(&account)), ["equipped"]);
+}
+
+#[test]
+fn test_event_metadata() {
+    let (sink, events) = ChannelSink::new();
+    let mut tracker = SpriteAccountTracker::new(Box::new(sink));
+    let address = Pubkey::new_unique();
+    let account = sprite_account(Pubkey::new_unique(), vec![]);
+
+    tracker
+        .update(
+            &AccountUpdate {
+                pubkey: address,
+                owner: sprite_manager::id(),
+                lamports: 1,
+                data: &account.try_to_vec().unwrap(),
+                write_version: 7,
+                signature: Some("signature".to_string()),
+            },
+            42,
+            false,
+        )
+        .unwrap();
+
+    let event = events.try_recv().unwrap();
+    assert_eq!(event.slot, 42);
+    assert_eq!(event.write_version, 7);
+    assert_eq!(event.signature.as_deref(), Some("signature"));
+    assert_eq!(event.sprite_account, address);
+    assert_eq!(event.base_mint, account.base_mint);
+}
+
+#[test]
+fn test_dropped_receiver_fails_update() {
+    let (sink, events) = ChannelSink::new();
+    let mut tracker = SpriteAccountTracker::new(Box::new(sink));
+    drop(events);
+
+    let data = sprite_account(Pubkey::new_unique(), vec![])
+        .try_to_vec()
+        .unwrap();
+    let update = AccountUpdate {
+        pubkey: Pubkey::new_unique(),
+        owner: sprite_manager::id(),
+        lamports: 1,
+        data: &data,
+        write_version: 1,
+        signature: None,
+    };
+    assert!(tracker.update(&update, 1, false).is_err());
+}
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("{}-{}", Pubkey::new_unique(), name))
+}
+
+fn publish_one(mut tracker: SpriteAccountTracker) -> SpriteAccount {
+    let account = sprite_account(Pubkey::new_unique(), vec![sprite(Pubkey::new_unique())]);
+    let update = AccountUpdate {
+        pubkey: Pubkey::new_unique(),
+        owner: sprite_manager::id(),
+        lamports: 1,
+        data: &account.try_to_vec().unwrap(),
+        write_version: 1,
+        signature: None,
+    };
+    tracker.update(&update, 1, false).unwrap();
+    account
+}
+
+#[test]
+fn test_file_sink() {
+    let path = temp_path("events.jsonl");
+    let account = publish_one(SpriteAccountTracker::new(Box::new(
+        FileSink::open(&path).unwrap(),
+    )));
+
+    let events = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<ChangeEvent>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert!(matches!(events[0].change, SpriteChange::SpriteAccountCreated));
+    assert!(matches!(
+        &events[1].change,
+        SpriteChange::SpriteStored { sprite } if sprite.mint == account.sprites[0].mint
+    ));
+    assert_eq!(events.len(), 2);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+/// Forwards writes once `gate` is released, standing in for a stalled reader.
+struct GatedWriter {
+    gate: Receiver<()>,
+    written: Sender<Vec<u8>>,
+}
+
+impl Write for GatedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let _ = self.gate.recv();
+        self.written.send(buf.to_vec()).unwrap();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_full_queue_drops_events() {
+    let (gate, gate_receiver) = mpsc::channel();
+    let (written, lines) = mpsc::channel();
+    let writer = QueuedWriter::new(
+        GatedWriter {
+            gate: gate_receiver,
+            written,
+        },
+        1,
+    );
+    let mut tracker = SpriteAccountTracker::new(Box::new(writer));
+
+    // Each new account publishes two events, none of which wait on the stalled writer.
+    for _ in 0..3 {
+        let data = sprite_account(Pubkey::new_unique(), vec![sprite(Pubkey::new_unique())])
+            .try_to_vec()
+            .unwrap();
+        let update = AccountUpdate {
+            pubkey: Pubkey::new_unique(),
+            owner: sprite_manager::id(),
+            lamports: 1,
+            data: &data,
+            write_version: 1,
+            signature: None,
+        };
+        tracker.update(&update, 1, false).unwrap();
+    }
+
+    // At most one line is being written and one is queued, the rest were dropped.
+    drop(gate);
+    drop(tracker);
+    let written = lines.iter().count();
+    assert!((1..=2).contains(&written), "{} lines written", written);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unix_socket_sink_from_config() {
+    use std::os::unix::net::UnixListener;
+
+    let socket = temp_path("events.sock");
+    let listener = UnixListener::bind(&socket).unwrap();
+
+    let config_path = temp_path("config.json");
+    std::fs::write(
+        &config_path,
+        serde_json::json!({
+            "libpath": "libsprite_manager_geyser.so",
+            "sink": { "unix_socket": socket },
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let config = PluginConfig::load(&config_path).unwrap();
+    assert!(matches!(&config.sink, SinkConfig::UnixSocket(path) if *path == socket));
+
+    let tracker = SpriteAccountTracker::new(config.sink.open().unwrap());
+    let (stream, _) = listener.accept().unwrap();
+    publish_one(tracker);
+
+    let lines = BufReader::new(stream)
+        .lines()
+        .map(|line| serde_json::from_str::<ChangeEvent>(&line.unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+
+    std::fs::remove_file(socket).unwrap();
+    std::fs::remove_file(config_path).unwrap();
+}
@@ -0,0 +1,20 @@
+use solana_sdk::pubkey::Pubkey;
+use sprite_manager_client::ClientError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IndexerError {
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error(transparent)]
+    Client(#[from] ClientError),
+
+    #[error("Failed to deserialize account {0}")]
+    Deserialize(Pubkey),
+
+    #[error("Invalid pubkey {0} in the index")]
+    InvalidPubkey(String),
+}
+
+pub type Result<T> = std::result::Result<T, IndexerError>;
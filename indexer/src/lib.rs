@@ -0,0 +1,501 @@
+//! Local SQLite index of sprite manager accounts.
+//!
+//! [`Indexer`] ingests `SpriteAccount` snapshots, whether from `getProgramAccounts`, from any
+//! [`Connection`] such as a program-test `BanksClient`, or from raw accounts, into three tables:
+//!
+//! * `sprite_accounts`: one row per sprite account with its raw data and the slot it was last
+//!   seen at.
+//! * `sprites`: one row per sprite stored in a sprite account.
+//! * `tags`: the perspective, style and custom tags of each sprite.
+//!
+//! [`Indexer::query`] runs the same [`SpriteQuery`] filters as `SpriteAccount::query` across every
+//! indexed account.
+//!
+//! Ingestion is incremental. An account whose data has not changed is left alone, a changed
+//! account has its sprite and tag rows rebuilt, and snapshots older than the indexed state are
+//! ignored.
+
+mod error;
+mod schema;
+
+pub use error::{IndexerError, Result};
+pub use sprite_manager::query::SpriteQuery;
+
+use std::{collections::HashSet, path::Path, str::FromStr};
+
+use rusqlite::{functions::FunctionFlags, params, params_from_iter, OptionalExtension};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+};
+use solana_sdk::{account::Account, clock::Slot, pubkey::Pubkey};
+use sprite_manager::{
+    query::text_matches,
+    state::{PerspectiveTags, SolanaAccount, Sprite, SpriteAccount, StyleTags},
+};
+use sprite_manager_client::{filters, ClientError, Connection};
+
+/// SQL name of [`text_matches`], registered on every connection so that text queries match
+/// exactly as they do on a decoded `SpriteAccount`.
+const TEXT_MATCHES: &str = "sprite_text_matches";
+
+/// What ingesting an account did to the index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IngestOutcome {
+    /// The account was not indexed before.
+    Inserted,
+    /// The account data changed and its sprites and tags were rebuilt.
+    Updated,
+    /// The account data is the same as the indexed data, only its slot was bumped.
+    Unchanged,
+    /// The snapshot is older than the indexed state and was skipped.
+    Stale,
+    /// The account was closed or is no longer a sprite account.
+    Removed,
+    /// The account is not a sprite account and was never indexed.
+    Ignored,
+}
+
+impl IngestOutcome {
+    /// Whether the index rows changed.
+    pub fn is_change(&self) -> bool {
+        matches!(
+            self,
+            IngestOutcome::Inserted | IngestOutcome::Updated | IngestOutcome::Removed
+        )
+    }
+}
+
+/// A sprite tag as stored in the `tags` table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Tag {
+    Perspective(PerspectiveTags),
+    Style(StyleTags),
+    Custom(String),
+}
+
+impl Tag {
+    /// The `kind` column of the tag: `perspective`, `style` or `custom`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Tag::Perspective(_) => "perspective",
+            Tag::Style(_) => "style",
+            Tag::Custom(_) => "custom",
+        }
+    }
+
+    /// The `tag` column of the tag, the variant name for perspective and style tags.
+    pub fn value(&self) -> String {
+        match self {
+            Tag::Perspective(tag) => format!("{:?}", tag),
+            Tag::Style(tag) => format!("{:?}", tag),
+            Tag::Custom(tag) => tag.clone(),
+        }
+    }
+
+    fn of(sprite: &Sprite) -> impl Iterator<Item = Tag> + '_ {
+        sprite
+            .perspective_tags
+            .iter()
+            .cloned()
+            .map(Tag::Perspective)
+            .chain(sprite.style_tags.iter().cloned().map(Tag::Style))
+            .chain(sprite.custom_tags.iter().cloned().map(Tag::Custom))
+    }
+}
+
+impl From<Tag> for SpriteQuery {
+    fn from(tag: Tag) -> Self {
+        match tag {
+            Tag::Perspective(tag) => SpriteQuery::Perspective(tag),
+            Tag::Style(tag) => SpriteQuery::Style(tag),
+            Tag::Custom(tag) => SpriteQuery::CustomTag(tag),
+        }
+    }
+}
+
+/// A sprite returned by an index query, with the sprite account it is stored in.
+#[derive(Clone, Debug)]
+pub struct IndexedSprite {
+    pub sprite_account: Pubkey,
+    pub base_mint: Pubkey,
+    pub sprite: Sprite,
+}
+
+pub struct Indexer {
+    db: rusqlite::Connection,
+}
+
+impl Indexer {
+    /// Opens the index at `path`, creating the database and its tables if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_database(rusqlite::Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_database(rusqlite::Connection::open_in_memory()?)
+    }
+
+    fn with_database(db: rusqlite::Connection) -> Result<Self> {
+        db.execute_batch(schema::SCHEMA)?;
+        db.create_scalar_function(
+            TEXT_MATCHES,
+            3,
+            FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+            |ctx| {
+                Ok(text_matches(
+                    &ctx.get::<String>(0)?,
+                    &ctx.get::<String>(1)?,
+                    &ctx.get::<String>(2)?,
+                ))
+            },
+        )?;
+        Ok(Self { db })
+    }
+
+    /// The underlying database, for queries the indexer does not provide.
+    pub fn database(&self) -> &rusqlite::Connection {
+        &self.db
+    }
+
+    /// Ingests the state of the account at `address` as of `slot`. `None` means the account does
+    /// not exist, which removes it from the index.
+    pub fn ingest_account(
+        &mut self,
+        address: &Pubkey,
+        account: Option<&Account>,
+        slot: Slot,
+    ) -> Result<IngestOutcome> {
+        let tx = self.db.transaction()?;
+        let outcome = ingest(&tx, address, account, slot)?;
+        tx.commit()?;
+        Ok(outcome)
+    }
+
+    /// Ingests a full snapshot of the program's accounts as of `slot`. Indexed accounts missing
+    /// from the snapshot are removed. Returns the number of accounts whose rows changed.
+    pub fn ingest_snapshot<I>(&mut self, accounts: I, slot: Slot) -> Result<usize>
+    where
+        I: IntoIterator<Item = (Pubkey, Account)>,
+    {
+        let tx = self.db.transaction()?;
+        let mut changed = 0;
+        let mut seen = HashSet::new();
+
+        for (address, account) in accounts {
+            if ingest(&tx, &address, Some(&account), slot)?.is_change() {
+                changed += 1;
+            }
+            seen.insert(address.to_string());
+        }
+
+        let indexed = tx
+            .prepare("SELECT address FROM sprite_accounts")?
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        for address in indexed.iter().filter(|address| !seen.contains(*address)) {
+            if ingest(&tx, &parse_pubkey(address)?, None, slot)?.is_change() {
+                changed += 1;
+            }
+        }
+
+        tx.commit()?;
+        Ok(changed)
+    }
+
+    /// Fetches `addresses` through `connection` and ingests them as of `slot`. This is how a
+    /// program-test bank is indexed, since `BanksClient` cannot list program accounts.
+    pub async fn refresh<C: Connection + Send>(
+        &mut self,
+        connection: &mut C,
+        addresses: &[Pubkey],
+        slot: Slot,
+    ) -> Result<Vec<IngestOutcome>> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            accounts.push(connection.get_account(address).await?);
+        }
+
+        let tx = self.db.transaction()?;
+        let outcomes = addresses
+            .iter()
+            .zip(&accounts)
+            .map(|(address, account)| ingest(&tx, address, account.as_ref(), slot))
+            .collect::<Result<Vec<_>>>()?;
+        tx.commit()?;
+        Ok(outcomes)
+    }
+
+    /// Snapshots every sprite account with `getProgramAccounts` and ingests it. Returns the number
+    /// of accounts whose rows changed.
+    pub async fn sync_program_accounts(&mut self, rpc: &RpcClient) -> Result<usize> {
+        let slot = rpc.get_slot().await.map_err(ClientError::from)?;
+        let mut accounts = Vec::new();
+        // A memcmp filter matches one key, so accounts in the original layout are listed apart.
+        for filters in [
+            filters::all_sprite_accounts(),
+            filters::unmigrated_sprite_accounts(),
+        ] {
+            let config = RpcProgramAccountsConfig {
+                filters: Some(filters),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(rpc.commitment()),
+                    min_context_slot: Some(slot),
+                    ..RpcAccountInfoConfig::default()
+                },
+                with_context: None,
+            };
+            accounts.extend(
+                rpc.get_program_accounts_with_config(&sprite_manager::id(), config)
+                    .await
+                    .map_err(ClientError::from)?,
+            );
+        }
+
+        self.ingest_snapshot(accounts, slot)
+    }
+
+    /// The slot the account at `address` was last ingested at, `None` if it is not indexed.
+    pub fn indexed_slot(&self, address: &Pubkey) -> Result<Option<Slot>> {
+        Ok(self
+            .db
+            .query_row(
+                "SELECT slot FROM sprite_accounts WHERE address = ?1",
+                params![address.to_string()],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()?
+            .map(|slot| slot as Slot))
+    }
+
+    /// The indexed sprite account of `base_mint`.
+    pub fn get_sprite_account(&self, base_mint: &Pubkey) -> Result<Option<SpriteAccount>> {
+        self.db
+            .query_row(
+                "SELECT address, data FROM sprite_accounts WHERE base_mint = ?1",
+                params![base_mint.to_string()],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?)),
+            )
+            .optional()?
+            .map(|(address, data)| decode(&parse_pubkey(&address)?, &data))
+            .transpose()
+    }
+
+    /// The base mints that currently have `sprite_mint` equipped.
+    pub fn base_mints_wearing(&self, sprite_mint: &Pubkey) -> Result<Vec<Pubkey>> {
+        self.db
+            .prepare(
+                "SELECT a.base_mint FROM sprites s
+                 JOIN sprite_accounts a ON a.address = s.sprite_account
+                 WHERE s.mint = ?1 AND s.equipped = 1
+                 ORDER BY a.base_mint",
+            )?
+            .query_map(params![sprite_mint.to_string()], |row| {
+                row.get::<_, String>(0)
+            })?
+            .map(|base_mint| parse_pubkey(&base_mint?))
+            .collect()
+    }
+
+    /// The sprites carrying every one of `tags`. No tags returns every indexed sprite.
+    pub fn sprites_with_tags(&self, tags: &[Tag]) -> Result<Vec<IndexedSprite>> {
+        self.query(&SpriteQuery::all(
+            tags.iter().cloned().map(SpriteQuery::from),
+        ))
+    }
+
+    /// The sprites matching `query`, ordered by base mint and then by their position in the
+    /// sprite account. Matches the same sprites as [`SpriteAccount::query`] on every indexed
+    /// account.
+    pub fn query(&self, query: &SpriteQuery) -> Result<Vec<IndexedSprite>> {
+        let mut params = Vec::new();
+        let sql = format!(
+            "SELECT a.address, a.data, s.mint FROM sprites s
+             JOIN sprite_accounts a ON a.address = s.sprite_account
+             WHERE {} ORDER BY a.base_mint, s.position",
+            condition(query, &mut params)
+        );
+
+        let rows = self
+            .db
+            .prepare(&sql)?
+            .query_map(params_from_iter(params), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Vec<u8>>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(|(address, data, mint)| {
+                let sprite_account = parse_pubkey(&address)?;
+                let account = decode(&sprite_account, &data)?;
+                let sprite = account
+                    .find_sprite(&parse_pubkey(&mint)?)
+                    .cloned()
+                    .ok_or(IndexerError::Deserialize(sprite_account))?;
+                Ok(IndexedSprite {
+                    sprite_account,
+                    base_mint: account.base_mint,
+                    sprite,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Translates `query` into a SQL condition over the sprite row `s`, appending its parameters.
+fn condition(query: &SpriteQuery, params: &mut Vec<String>) -> String {
+    let tag = |tag: Tag, params: &mut Vec<String>| {
+        params.push(tag.kind().to_string());
+        params.push(tag.value());
+        format!(
+            "EXISTS (SELECT 1 FROM tags t WHERE t.sprite_account = s.sprite_account
+             AND t.mint = s.mint AND t.kind = ?{} AND t.tag = ?{})",
+            params.len() - 1,
+            params.len()
+        )
+    };
+    let join = |queries: &[SpriteQuery], op: &str, empty: &str, params: &mut Vec<String>| {
+        if queries.is_empty() {
+            return empty.to_string();
+        }
+        let conditions = queries
+            .iter()
+            .map(|query| condition(query, params))
+            .collect::<Vec<_>>();
+        format!("({})", conditions.join(op))
+    };
+
+    match query {
+        SpriteQuery::Perspective(perspective) => tag(Tag::Perspective(perspective.clone()), params),
+        SpriteQuery::Style(style) => tag(Tag::Style(style.clone()), params),
+        SpriteQuery::CustomTag(custom) => tag(Tag::Custom(custom.clone()), params),
+        SpriteQuery::Text(text) => {
+            params.push(text.clone());
+            format!("{}(s.name, s.description, ?{})", TEXT_MATCHES, params.len())
+        }
+        SpriteQuery::And(queries) => join(queries, " AND ", "1", params),
+        SpriteQuery::Or(queries) => join(queries, " OR ", "0", params),
+        SpriteQuery::Not(query) => format!("NOT {}", condition(query, params)),
+    }
+}
+
+fn ingest(
+    db: &rusqlite::Connection,
+    address: &Pubkey,
+    account: Option<&Account>,
+    slot: Slot,
+) -> Result<IngestOutcome> {
+    let key = address.to_string();
+    let indexed = db
+        .query_row(
+            "SELECT slot, data FROM sprite_accounts WHERE address = ?1",
+            params![key],
+            |row| Ok((row.get::<_, i64>(0)? as Slot, row.get::<_, Vec<u8>>(1)?)),
+        )
+        .optional()?;
+
+    if let Some((indexed_slot, _)) = indexed {
+        if indexed_slot > slot {
+            return Ok(IngestOutcome::Stale);
+        }
+    }
+
+    let data = match account {
+        Some(account) if is_sprite_account(account) => &account.data,
+        _ if indexed.is_some() => {
+            db.execute(
+                "DELETE FROM sprite_accounts WHERE address = ?1",
+                params![key],
+            )?;
+            return Ok(IngestOutcome::Removed);
+        }
+        _ => return Ok(IngestOutcome::Ignored),
+    };
+
+    if let Some((_, indexed_data)) = &indexed {
+        if indexed_data == data {
+            db.execute(
+                "UPDATE sprite_accounts SET slot = ?2 WHERE address = ?1",
+                params![key, slot as i64],
+            )?;
+            return Ok(IngestOutcome::Unchanged);
+        }
+    }
+
+    let sprite_account = decode(address, data)?;
+    db.execute(
+        "DELETE FROM sprite_accounts WHERE address = ?1",
+        params![key],
+    )?;
+    db.execute(
+        "INSERT INTO sprite_accounts (address, base_mint, slot, created_at, updated_at, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            key,
+            sprite_account.base_mint.to_string(),
+            slot as i64,
+            sprite_account.created_at.unix_timestamp,
+            sprite_account.updated_at.unix_timestamp,
+            data,
+        ],
+    )?;
+
+    // Rows are keyed by mint, so a mint stored twice keeps only its first sprite, the one
+    // `SpriteAccount::find_sprite` returns.
+    let mut mints = HashSet::new();
+    for (position, sprite) in sprite_account.sprites.iter().enumerate() {
+        if !mints.insert(sprite.mint) {
+            continue;
+        }
+        let mint = sprite.mint.to_string();
+        db.execute(
+            "INSERT INTO sprites (sprite_account, position, mint, name, description,
+                 equipment_slot, z_order, equipped, family, artist)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                key,
+                position as i64,
+                mint,
+                sprite.name,
+                sprite.description,
+                format!("{:?}", sprite.slot),
+                sprite.z_order,
+                sprite.equipped,
+                sprite.family.map(|family| family.to_string()),
+                sprite.artist.to_string(),
+            ],
+        )?;
+        for tag in Tag::of(sprite) {
+            db.execute(
+                "INSERT INTO tags (sprite_account, mint, kind, tag) VALUES (?1, ?2, ?3, ?4)",
+                params![key, mint, tag.kind(), tag.value()],
+            )?;
+        }
+    }
+
+    Ok(if indexed.is_some() {
+        IngestOutcome::Updated
+    } else {
+        IngestOutcome::Inserted
+    })
+}
+
+fn is_sprite_account(account: &Account) -> bool {
+    account.owner == sprite_manager::id()
+        && account.lamports > 0
+        && SpriteAccount::has_sprite_account_key(&account.data)
+}
+
+fn decode(address: &Pubkey, data: &[u8]) -> Result<SpriteAccount> {
+    SpriteAccount::safe_deserialize(data).map_err(|_| IndexerError::Deserialize(*address))
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| IndexerError::InvalidPubkey(value.to_string()))
+}
@@ -0,0 +1,43 @@
+/// Creates the index tables. Sprite and tag rows cascade from their sprite account, so replacing
+/// or removing a `sprite_accounts` row is enough to keep the other tables consistent.
+pub const SCHEMA: &str = "
+PRAGMA foreign_keys = ON;
+
+CREATE TABLE IF NOT EXISTS sprite_accounts (
+    address     TEXT PRIMARY KEY,
+    base_mint   TEXT NOT NULL,
+    slot        INTEGER NOT NULL,
+    created_at  INTEGER NOT NULL,
+    updated_at  INTEGER NOT NULL,
+    data        BLOB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS sprite_accounts_base_mint ON sprite_accounts (base_mint);
+
+CREATE TABLE IF NOT EXISTS sprites (
+    sprite_account  TEXT NOT NULL REFERENCES sprite_accounts (address) ON DELETE CASCADE,
+    position        INTEGER NOT NULL,
+    mint            TEXT NOT NULL,
+    name            TEXT NOT NULL,
+    description     TEXT NOT NULL,
+    equipment_slot  TEXT NOT NULL,
+    z_order         INTEGER NOT NULL,
+    equipped        INTEGER NOT NULL,
+    family          TEXT,
+    artist          TEXT NOT NULL,
+    PRIMARY KEY (sprite_account, mint)
+);
+
+CREATE INDEX IF NOT EXISTS sprites_mint ON sprites (mint);
+
+CREATE TABLE IF NOT EXISTS tags (
+    sprite_account  TEXT NOT NULL,
+    mint            TEXT NOT NULL,
+    kind            TEXT NOT NULL,
+    tag             TEXT NOT NULL,
+    FOREIGN KEY (sprite_account, mint) REFERENCES sprites (sprite_account, mint) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS tags_kind_tag ON tags (kind, tag);
+CREATE INDEX IF NOT EXISTS tags_sprite ON tags (sprite_account, mint);
+";
@@ -0,0 +1,393 @@
+use borsh::BorshSerialize;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use sprite_manager::{
+    pda::find_sprite_address,
+    state::{Key, PerspectiveTags, SolanaAccount, Sprite, SpriteAccount, StyleTags},
+};
+use sprite_manager_indexer::{Indexer, IngestOutcome, SpriteQuery, Tag};
+#[cfg(feature = "test-bpf")]
+use {
+    solana_program_test::*,
+    solana_sdk::{signer::Signer, transaction::Transaction},
+    sprite_manager::{
+        builders::EquipSpriteBuilder, instruction::StoreSpriteArgs, state::EquipmentSlot,
+    },
+    sprite_manager_test_utils::*,
+};
+
+fn sprite(
+    mint: Pubkey,
+    perspective_tags: Vec<PerspectiveTags>,
+    style_tags: Vec<StyleTags>,
+    equipped: bool,
+) -> Sprite {
+    Sprite {
+        name: "sprite".to_string(),
+        mint,
+        perspective_tags,
+        style_tags,
+        custom_tags: vec!["summer".to_string()],
+        equipped,
+        ..Sprite::default()
+    }
+}
+
+fn account(base_mint: Pubkey, sprites: Vec<Sprite>) -> (Pubkey, Account) {
+    let sprite_account = SpriteAccount {
+        key: Key::SpriteAccount,
+        base_mint,
+        sprites,
+        ..SpriteAccount::default()
+    };
+    let account = Account {
+        lamports: 1_000_000,
+        data: sprite_account.try_to_vec().unwrap(),
+        owner: sprite_manager::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    (find_sprite_address(&base_mint).0, account)
+}
+
+#[test]
+fn test_queries() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let hat = Pubkey::new_unique();
+    let shirt = Pubkey::new_unique();
+    let base_a = Pubkey::new_unique();
+    let base_b = Pubkey::new_unique();
+
+    let snapshot = vec![
+        account(
+            base_a,
+            vec![
+                sprite(
+                    hat,
+                    vec![PerspectiveTags::TopDown],
+                    vec![StyleTags::Pixel],
+                    true,
+                ),
+                sprite(
+                    shirt,
+                    vec![PerspectiveTags::RPG],
+                    vec![StyleTags::Pixel],
+                    true,
+                ),
+            ],
+        ),
+        account(
+            base_b,
+            vec![sprite(
+                Pubkey::new_unique(),
+                vec![PerspectiveTags::TopDown],
+                vec![StyleTags::Vector],
+                false,
+            )],
+        ),
+    ];
+    assert_eq!(indexer.ingest_snapshot(snapshot, 10).unwrap(), 2);
+
+    assert_eq!(indexer.base_mints_wearing(&hat).unwrap(), vec![base_a]);
+    assert_eq!(
+        indexer
+            .get_sprite_account(&base_a)
+            .unwrap()
+            .unwrap()
+            .sprites
+            .len(),
+        2
+    );
+    assert!(indexer
+        .get_sprite_account(&Pubkey::new_unique())
+        .unwrap()
+        .is_none());
+
+    let pixel_top_down = indexer
+        .sprites_with_tags(&[
+            Tag::Style(StyleTags::Pixel),
+            Tag::Perspective(PerspectiveTags::TopDown),
+        ])
+        .unwrap();
+    assert_eq!(pixel_top_down.len(), 1);
+    assert_eq!(pixel_top_down[0].base_mint, base_a);
+    assert_eq!(pixel_top_down[0].sprite.mint, hat);
+
+    assert_eq!(
+        indexer
+            .sprites_with_tags(&[Tag::Custom("summer".to_string())])
+            .unwrap()
+            .len(),
+        3
+    );
+    assert_eq!(indexer.sprites_with_tags(&[]).unwrap().len(), 3);
+}
+
+#[test]
+fn test_incremental_updates() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let hat = Pubkey::new_unique();
+    let base_mint = Pubkey::new_unique();
+
+    let (address, worn) = account(
+        base_mint,
+        vec![sprite(hat, vec![], vec![StyleTags::Pixel], true)],
+    );
+    let (_, stowed) = account(
+        base_mint,
+        vec![sprite(hat, vec![], vec![StyleTags::Vector], false)],
+    );
+
+    assert_eq!(
+        indexer.ingest_account(&address, Some(&worn), 5).unwrap(),
+        IngestOutcome::Inserted
+    );
+    assert_eq!(
+        indexer.ingest_account(&address, Some(&worn), 6).unwrap(),
+        IngestOutcome::Unchanged
+    );
+    assert_eq!(indexer.indexed_slot(&address).unwrap(), Some(6));
+
+    assert_eq!(
+        indexer.ingest_account(&address, Some(&stowed), 7).unwrap(),
+        IngestOutcome::Updated
+    );
+    assert!(indexer.base_mints_wearing(&hat).unwrap().is_empty());
+    assert!(indexer
+        .sprites_with_tags(&[Tag::Style(StyleTags::Pixel)])
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        indexer
+            .sprites_with_tags(&[Tag::Style(StyleTags::Vector)])
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // An older snapshot must not roll the index back.
+    assert_eq!(
+        indexer.ingest_account(&address, Some(&worn), 6).unwrap(),
+        IngestOutcome::Stale
+    );
+    assert!(indexer.base_mints_wearing(&hat).unwrap().is_empty());
+
+    let mut config = worn.clone();
+    config.data[0] = Key::ProgramConfig as u8;
+    assert_eq!(
+        indexer
+            .ingest_account(&Pubkey::new_unique(), Some(&config), 8)
+            .unwrap(),
+        IngestOutcome::Ignored
+    );
+
+    assert_eq!(
+        indexer.ingest_account(&address, None, 8).unwrap(),
+        IngestOutcome::Removed
+    );
+    assert!(indexer.sprites_with_tags(&[]).unwrap().is_empty());
+    let tags: i64 = indexer
+        .database()
+        .query_row("SELECT COUNT(*) FROM tags", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(tags, 0);
+
+    // A full snapshot drops accounts that are no longer returned.
+    indexer.ingest_account(&address, Some(&worn), 9).unwrap();
+    assert_eq!(indexer.ingest_snapshot(vec![], 10).unwrap(), 1);
+    assert_eq!(indexer.indexed_slot(&address).unwrap(), None);
+}
+
+#[test]
+fn test_repeated_mint() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let hat = Pubkey::new_unique();
+    let base_mint = Pubkey::new_unique();
+    let (address, duplicated) = account(
+        base_mint,
+        vec![
+            sprite(hat, vec![], vec![StyleTags::Pixel], true),
+            sprite(hat, vec![], vec![StyleTags::Vector], false),
+        ],
+    );
+
+    assert_eq!(
+        indexer
+            .ingest_account(&address, Some(&duplicated), 1)
+            .unwrap(),
+        IngestOutcome::Inserted
+    );
+    assert_eq!(indexer.base_mints_wearing(&hat).unwrap(), vec![base_mint]);
+    let sprites = indexer.sprites_with_tags(&[]).unwrap();
+    assert_eq!(sprites.len(), 1);
+    assert_eq!(sprites[0].sprite.style_tags, vec![StyleTags::Pixel]);
+
+    // The rest of the snapshot is still indexed.
+    let snapshot = vec![
+        (address, duplicated),
+        account(
+            Pubkey::new_unique(),
+            vec![sprite(Pubkey::new_unique(), vec![], vec![], false)],
+        ),
+    ];
+    assert_eq!(indexer.ingest_snapshot(snapshot, 2).unwrap(), 1);
+    assert_eq!(indexer.sprites_with_tags(&[]).unwrap().len(), 2);
+}
+
+#[test]
+fn test_sprite_query_matches_account_query() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let named = |name: &str, description: &str, style_tags: Vec<StyleTags>| Sprite {
+        name: name.to_string(),
+        description: description.to_string(),
+        ..sprite(
+            Pubkey::new_unique(),
+            vec![PerspectiveTags::TopDown],
+            style_tags,
+            false,
+        )
+    };
+
+    let snapshot = vec![
+        account(
+            Pubkey::new_unique(),
+            vec![
+                named("Straw Hat", "Wide brim", vec![StyleTags::Pixel]),
+                named("Épée", "A light sword", vec![StyleTags::Vector]),
+            ],
+        ),
+        account(
+            Pubkey::new_unique(),
+            vec![named("Cape", "Worn under a HAT", vec![StyleTags::Pixel])],
+        ),
+    ];
+    let accounts = snapshot
+        .iter()
+        .map(|(_, account)| SpriteAccount::safe_deserialize(&account.data).unwrap())
+        .collect::<Vec<_>>();
+    indexer.ingest_snapshot(snapshot, 1).unwrap();
+
+    let queries = [
+        SpriteQuery::text("hat"),
+        SpriteQuery::text("ÉPÉE"),
+        SpriteQuery::text("hat") & !SpriteQuery::Style(StyleTags::Pixel),
+        SpriteQuery::Style(StyleTags::Vector) | SpriteQuery::text("cape"),
+        !(SpriteQuery::custom_tag("summer") & SpriteQuery::Perspective(PerspectiveTags::TopDown)),
+        SpriteQuery::any([]),
+        SpriteQuery::all([]),
+    ];
+    for query in &queries {
+        let mut expected = accounts
+            .iter()
+            .flat_map(|account| account.query(query))
+            .map(|sprite| sprite.mint)
+            .collect::<Vec<_>>();
+        let mut indexed = indexer
+            .query(query)
+            .unwrap()
+            .into_iter()
+            .map(|sprite| sprite.sprite.mint)
+            .collect::<Vec<_>>();
+        expected.sort();
+        indexed.sort();
+        assert_eq!(indexed, expected, "{:?}", query);
+    }
+
+    let matched = indexer.query(&SpriteQuery::text("épée")).unwrap();
+    assert_eq!(matched.len(), 1);
+    assert_eq!(matched[0].sprite.name, "Épée");
+}
+
+#[test]
+fn test_persists_to_disk() {
+    let path = std::env::temp_dir().join(format!("sprite-indexer-{}.db", Pubkey::new_unique()));
+    let hat = Pubkey::new_unique();
+    let base_mint = Pubkey::new_unique();
+    let (address, worn) = account(base_mint, vec![sprite(hat, vec![], vec![], true)]);
+
+    Indexer::open(&path)
+        .unwrap()
+        .ingest_account(&address, Some(&worn), 1)
+        .unwrap();
+    let indexer = Indexer::open(&path).unwrap();
+    assert_eq!(indexer.base_mints_wearing(&hat).unwrap(), vec![base_mint]);
+
+    drop(indexer);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_refresh_from_bank() {
+    let mut context = program_test().start_with_context().await;
+    let (base, _, _) = create_nft(&mut context, false, None).await;
+    let (hat, _, _) = create_nft(&mut context, false, None).await;
+    let (address, _) = create_sprite_account_helper(&mut context, &base).await;
+
+    store_sprite_helper(
+        &mut context,
+        &base,
+        &hat,
+        StoreSpriteArgs {
+            name: "Straw hat".to_string(),
+            description: "A wide-brimmed hat".to_string(),
+            perspective_tags: vec![PerspectiveTags::TopDown],
+            style_tags: vec![StyleTags::Pixel],
+            slot: EquipmentSlot::Hat,
+            z_order: 3,
+            ..StoreSpriteArgs::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let slot = context.banks_client.get_root_slot().await.unwrap();
+    assert_eq!(
+        indexer
+            .refresh(&mut context.banks_client, &[address], slot)
+            .await
+            .unwrap(),
+        vec![IngestOutcome::Inserted]
+    );
+    assert!(indexer
+        .base_mints_wearing(&hat.mint.pubkey())
+        .unwrap()
+        .is_empty());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[EquipSpriteBuilder::new(
+            base.mint.pubkey(),
+            hat.mint.pubkey(),
+            context.payer.pubkey(),
+        )
+        .base_token_account(base.token.pubkey())
+        .instruction()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let slot = context.banks_client.get_root_slot().await.unwrap();
+    assert_eq!(
+        indexer
+            .refresh(&mut context.banks_client, &[address], slot)
+            .await
+            .unwrap(),
+        vec![IngestOutcome::Updated]
+    );
+    assert_eq!(
+        indexer.base_mints_wearing(&hat.mint.pubkey()).unwrap(),
+        vec![base.mint.pubkey()]
+    );
+
+    let sprites = indexer
+        .sprites_with_tags(&[
+            Tag::Style(StyleTags::Pixel),
+            Tag::Perspective(PerspectiveTags::TopDown),
+        ])
+        .unwrap();
+    assert_eq!(sprites.len(), 1);
+    assert_eq!(sprites[0].sprite_account, address);
+    assert_eq!(sprites[0].sprite.name, "Straw hat");
+}
@@ -9,6 +9,8 @@ import * as web3 from '@solana/web3.js';
 import * as beetSolana from '@metaplex-foundation/beet-solana';
 import * as beet from '@metaplex-foundation/beet';
 import { Key, keyBeet } from '../types/Key';
+import { Timestamp, timestampBeet } from '../types/Timestamp';
+import { SpriteFamily, spriteFamilyBeet } from '../types/SpriteFamily';
 import { Sprite, spriteBeet } from '../types/Sprite';
 
 /**
@@ -19,6 +21,9 @@ import { Sprite, spriteBeet } from '../types/Sprite';
 export type SpriteAccountArgs = {
   key: Key;
   baseMint: web3.PublicKey;
+  createdAt: Timestamp;
+  updatedAt: Timestamp;
+  families: SpriteFamily[];
   sprites: Sprite[];
 };
 /**
@@ -32,6 +37,9 @@ export class SpriteAccount implements SpriteAccountArgs {
   private constructor(
     readonly key: Key,
     readonly baseMint: web3.PublicKey,
+    readonly createdAt: Timestamp,
+    readonly updatedAt: Timestamp,
+    readonly families: SpriteFamily[],
     readonly sprites: Sprite[],
   ) {}
 
@@ -39,7 +47,14 @@ export class SpriteAccount implements SpriteAccountArgs {
    * Creates a {@link SpriteAccount} instance from the provided args.
    */
   static fromArgs(args: SpriteAccountArgs) {
-    return new SpriteAccount(args.key, args.baseMint, args.sprites);
+    return new SpriteAccount(
+      args.key,
+      args.baseMint,
+      args.createdAt,
+      args.updatedAt,
+      args.families,
+      args.sprites,
+    );
   }
 
   /**
@@ -135,6 +150,9 @@ export class SpriteAccount implements SpriteAccountArgs {
     return {
       key: 'Key.' + Key[this.key],
       baseMint: this.baseMint.toBase58(),
+      createdAt: this.createdAt,
+      updatedAt: this.updatedAt,
+      families: this.families,
       sprites: this.sprites,
     };
   }
@@ -148,6 +166,9 @@ export const spriteAccountBeet = new beet.FixableBeetStruct<SpriteAccount, Sprit
   [
     ['key', keyBeet],
     ['baseMint', beetSolana.publicKey],
+    ['createdAt', timestampBeet],
+    ['updatedAt', timestampBeet],
+    ['families', beet.array(spriteFamilyBeet)],
     ['sprites', beet.array(spriteBeet)],
   ],
   SpriteAccount.fromArgs,
@@ -1,5 +1,7 @@
+export * from './ProgramConfig';
 export * from './SpriteAccount';
 
+import { ProgramConfig } from './ProgramConfig';
 import { SpriteAccount } from './SpriteAccount';
 
-export const accountProviders = { SpriteAccount };
+export const accountProviders = { ProgramConfig, SpriteAccount };
@@ -154,6 +154,495 @@ export class NumericalOverflowError extends Error {
 createErrorFromCodeLookup.set(0x6, () => new NumericalOverflowError());
 createErrorFromNameLookup.set('NumericalOverflow', () => new NumericalOverflowError());
 
+/**
+ * SpriteNotFound: 'Sprite not found'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class SpriteNotFoundError extends Error {
+  readonly code: number = 0x7;
+  readonly name: string = 'SpriteNotFound';
+  constructor() {
+    super('Sprite not found');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, SpriteNotFoundError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x7, () => new SpriteNotFoundError());
+createErrorFromNameLookup.set('SpriteNotFound', () => new SpriteNotFoundError());
+
+/**
+ * SlotOccupied: 'Equipment slot already occupied'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class SlotOccupiedError extends Error {
+  readonly code: number = 0x8;
+  readonly name: string = 'SlotOccupied';
+  constructor() {
+    super('Equipment slot already occupied');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, SlotOccupiedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x8, () => new SlotOccupiedError());
+createErrorFromNameLookup.set('SlotOccupied', () => new SlotOccupiedError());
+
+/**
+ * SpriteNotEquipped: 'Sprite is not equipped'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class SpriteNotEquippedError extends Error {
+  readonly code: number = 0x9;
+  readonly name: string = 'SpriteNotEquipped';
+  constructor() {
+    super('Sprite is not equipped');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, SpriteNotEquippedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x9, () => new SpriteNotEquippedError());
+createErrorFromNameLookup.set('SpriteNotEquipped', () => new SpriteNotEquippedError());
+
+/**
+ * NotBaseTokenHolder: 'Signer does not hold the base token'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class NotBaseTokenHolderError extends Error {
+  readonly code: number = 0xa;
+  readonly name: string = 'NotBaseTokenHolder';
+  constructor() {
+    super('Signer does not hold the base token');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, NotBaseTokenHolderError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0xa, () => new NotBaseTokenHolderError());
+createErrorFromNameLookup.set('NotBaseTokenHolder', () => new NotBaseTokenHolderError());
+
+/**
+ * InvalidPalette: 'Invalid palette'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidPaletteError extends Error {
+  readonly code: number = 0xb;
+  readonly name: string = 'InvalidPalette';
+  constructor() {
+    super('Invalid palette');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidPaletteError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0xb, () => new InvalidPaletteError());
+createErrorFromNameLookup.set('InvalidPalette', () => new InvalidPaletteError());
+
+/**
+ * PaletteVariantNotFound: 'Palette variant not found'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class PaletteVariantNotFoundError extends Error {
+  readonly code: number = 0xc;
+  readonly name: string = 'PaletteVariantNotFound';
+  constructor() {
+    super('Palette variant not found');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, PaletteVariantNotFoundError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0xc, () => new PaletteVariantNotFoundError());
+createErrorFromNameLookup.set('PaletteVariantNotFound', () => new PaletteVariantNotFoundError());
+
+/**
+ * NoVerifiedCreator: 'Sprite metadata has no verified creator'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class NoVerifiedCreatorError extends Error {
+  readonly code: number = 0xd;
+  readonly name: string = 'NoVerifiedCreator';
+  constructor() {
+    super('Sprite metadata has no verified creator');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, NoVerifiedCreatorError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0xd, () => new NoVerifiedCreatorError());
+createErrorFromNameLookup.set('NoVerifiedCreator', () => new NoVerifiedCreatorError());
+
+/**
+ * MetadataMintMismatch: 'Metadata does not match the sprite mint'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class MetadataMintMismatchError extends Error {
+  readonly code: number = 0xe;
+  readonly name: string = 'MetadataMintMismatch';
+  constructor() {
+    super('Metadata does not match the sprite mint');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, MetadataMintMismatchError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0xe, () => new MetadataMintMismatchError());
+createErrorFromNameLookup.set('MetadataMintMismatch', () => new MetadataMintMismatchError());
+
+/**
+ * InvalidAttributes: 'Invalid sprite attributes'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidAttributesError extends Error {
+  readonly code: number = 0xf;
+  readonly name: string = 'InvalidAttributes';
+  constructor() {
+    super('Invalid sprite attributes');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidAttributesError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0xf, () => new InvalidAttributesError());
+createErrorFromNameLookup.set('InvalidAttributes', () => new InvalidAttributesError());
+
+/**
+ * DuplicateAttributeKey: 'Duplicate attribute key'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class DuplicateAttributeKeyError extends Error {
+  readonly code: number = 0x10;
+  readonly name: string = 'DuplicateAttributeKey';
+  constructor() {
+    super('Duplicate attribute key');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, DuplicateAttributeKeyError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x10, () => new DuplicateAttributeKeyError());
+createErrorFromNameLookup.set('DuplicateAttributeKey', () => new DuplicateAttributeKeyError());
+
+/**
+ * SpriteHasNoFamily: 'Sprite does not belong to a family'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class SpriteHasNoFamilyError extends Error {
+  readonly code: number = 0x11;
+  readonly name: string = 'SpriteHasNoFamily';
+  constructor() {
+    super('Sprite does not belong to a family');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, SpriteHasNoFamilyError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x11, () => new SpriteHasNoFamilyError());
+createErrorFromNameLookup.set('SpriteHasNoFamily', () => new SpriteHasNoFamilyError());
+
+/**
+ * InvalidRendition: 'Invalid rendition'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidRenditionError extends Error {
+  readonly code: number = 0x12;
+  readonly name: string = 'InvalidRendition';
+  constructor() {
+    super('Invalid rendition');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidRenditionError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x12, () => new InvalidRenditionError());
+createErrorFromNameLookup.set('InvalidRendition', () => new InvalidRenditionError());
+
+/**
+ * ProgramPaused: 'Program is paused'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class ProgramPausedError extends Error {
+  readonly code: number = 0x13;
+  readonly name: string = 'ProgramPaused';
+  constructor() {
+    super('Program is paused');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, ProgramPausedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x13, () => new ProgramPausedError());
+createErrorFromNameLookup.set('ProgramPaused', () => new ProgramPausedError());
+
+/**
+ * InvalidAdmin: 'Signer is not the program admin'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidAdminError extends Error {
+  readonly code: number = 0x14;
+  readonly name: string = 'InvalidAdmin';
+  constructor() {
+    super('Signer is not the program admin');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidAdminError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x14, () => new InvalidAdminError());
+createErrorFromNameLookup.set('InvalidAdmin', () => new InvalidAdminError());
+
+/**
+ * NotPendingAdmin: 'Signer is not the pending admin'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class NotPendingAdminError extends Error {
+  readonly code: number = 0x15;
+  readonly name: string = 'NotPendingAdmin';
+  constructor() {
+    super('Signer is not the pending admin');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, NotPendingAdminError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x15, () => new NotPendingAdminError());
+createErrorFromNameLookup.set('NotPendingAdmin', () => new NotPendingAdminError());
+
+/**
+ * ConfigAlreadyInitialized: 'Program config already initialized'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class ConfigAlreadyInitializedError extends Error {
+  readonly code: number = 0x16;
+  readonly name: string = 'ConfigAlreadyInitialized';
+  constructor() {
+    super('Program config already initialized');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, ConfigAlreadyInitializedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x16, () => new ConfigAlreadyInitializedError());
+createErrorFromNameLookup.set(
+  'ConfigAlreadyInitialized',
+  () => new ConfigAlreadyInitializedError(),
+);
+
+/**
+ * LimitExceeded: 'Configured size limit exceeded'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class LimitExceededError extends Error {
+  readonly code: number = 0x17;
+  readonly name: string = 'LimitExceeded';
+  constructor() {
+    super('Configured size limit exceeded');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, LimitExceededError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x17, () => new LimitExceededError());
+createErrorFromNameLookup.set('LimitExceeded', () => new LimitExceededError());
+
+/**
+ * InvalidTreasury: 'Invalid treasury account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidTreasuryError extends Error {
+  readonly code: number = 0x18;
+  readonly name: string = 'InvalidTreasury';
+  constructor() {
+    super('Invalid treasury account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidTreasuryError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x18, () => new InvalidTreasuryError());
+createErrorFromNameLookup.set('InvalidTreasury', () => new InvalidTreasuryError());
+
+/**
+ * InvalidTokenAccount: 'Invalid sprite token account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidTokenAccountError extends Error {
+  readonly code: number = 0x19;
+  readonly name: string = 'InvalidTokenAccount';
+  constructor() {
+    super('Invalid sprite token account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidTokenAccountError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x19, () => new InvalidTokenAccountError());
+createErrorFromNameLookup.set('InvalidTokenAccount', () => new InvalidTokenAccountError());
+
+/**
+ * VariantNotActive: 'Sprite is not the active variant of its family'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class VariantNotActiveError extends Error {
+  readonly code: number = 0x1a;
+  readonly name: string = 'VariantNotActive';
+  constructor() {
+    super('Sprite is not the active variant of its family');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, VariantNotActiveError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1a, () => new VariantNotActiveError());
+createErrorFromNameLookup.set('VariantNotActive', () => new VariantNotActiveError());
+
+/**
+ * NotUpgradeAuthority: 'Signer is not the program upgrade authority'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class NotUpgradeAuthorityError extends Error {
+  readonly code: number = 0x1b;
+  readonly name: string = 'NotUpgradeAuthority';
+  constructor() {
+    super('Signer is not the program upgrade authority');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, NotUpgradeAuthorityError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1b, () => new NotUpgradeAuthorityError());
+createErrorFromNameLookup.set('NotUpgradeAuthority', () => new NotUpgradeAuthorityError());
+
+/**
+ * SpriteAlreadyStored: 'Sprite is already stored'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class SpriteAlreadyStoredError extends Error {
+  readonly code: number = 0x1c;
+  readonly name: string = 'SpriteAlreadyStored';
+  constructor() {
+    super('Sprite is already stored');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, SpriteAlreadyStoredError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1c, () => new SpriteAlreadyStoredError());
+createErrorFromNameLookup.set('SpriteAlreadyStored', () => new SpriteAlreadyStoredError());
+
+/**
+ * SpriteAccountNotMigrated: 'Sprite account uses the original layout and must be migrated'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class SpriteAccountNotMigratedError extends Error {
+  readonly code: number = 0x1d;
+  readonly name: string = 'SpriteAccountNotMigrated';
+  constructor() {
+    super('Sprite account uses the original layout and must be migrated');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, SpriteAccountNotMigratedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1d, () => new SpriteAccountNotMigratedError());
+createErrorFromNameLookup.set(
+  'SpriteAccountNotMigrated',
+  () => new SpriteAccountNotMigratedError(),
+);
+
+/**
+ * SpriteAccountAlreadyMigrated: 'Sprite account already uses the current layout'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class SpriteAccountAlreadyMigratedError extends Error {
+  readonly code: number = 0x1e;
+  readonly name: string = 'SpriteAccountAlreadyMigrated';
+  constructor() {
+    super('Sprite account already uses the current layout');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, SpriteAccountAlreadyMigratedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x1e, () => new SpriteAccountAlreadyMigratedError());
+createErrorFromNameLookup.set(
+  'SpriteAccountAlreadyMigrated',
+  () => new SpriteAccountAlreadyMigratedError(),
+);
+
 /**
  * Attempts to resolve a custom program error from the provided error code.
  * @category Errors
@@ -28,6 +28,7 @@ export const CreateSpriteAccountStruct = new beet.BeetArgsStruct<{
  * @property [**signer**] creator The creator of the global sprite account
  * @property [] tokenMetadataProgram Token Metadata program
  * @property [] sysvarInstructions Instructions sysvar account
+ * @property [] config Program config PDA
  * @category Instructions
  * @category CreateSpriteAccount
  * @category generated
@@ -43,6 +44,7 @@ export type CreateSpriteAccountInstructionAccounts = {
   tokenMetadataProgram: web3.PublicKey;
   systemProgram?: web3.PublicKey;
   sysvarInstructions: web3.PublicKey;
+  config: web3.PublicKey;
 };
 
 export const createSpriteAccountInstructionDiscriminator = 0;
@@ -113,6 +115,11 @@ export function createCreateSpriteAccountInstruction(
       isWritable: false,
       isSigner: false,
     },
+    {
+      pubkey: accounts.config,
+      isWritable: false,
+      isSigner: false,
+    },
   ];
 
   const ix = new web3.TransactionInstruction({
@@ -39,12 +39,15 @@ export const StoreSpriteStruct = new beet.FixableBeetArgsStruct<
  * @property [_writable_] escrow Escrow account
  * @property [] baseMint Mint account of the base token
  * @property [] spriteMint Mint account of the sprite token
+ * @property [] spriteMetadata Metadata account of the sprite token
  * @property [] spriteMintSrc Source account of the sprite token
  * @property [] spriteMintDst Destination account of the sprite token
  * @property [_writable_, **signer**] payer The creator of the account and manager of the sprite
  * @property [_writable_] spritePda The PDA for sprite data
  * @property [] splToken Token program
  * @property [] splAssociatedToken Associated token account program
+ * @property [] config Program config PDA
+ * @property [_writable_] treasury Treasury receiving the store fee
  * @category Instructions
  * @category StoreSprite
  * @category generated
@@ -53,6 +56,7 @@ export type StoreSpriteInstructionAccounts = {
   escrow: web3.PublicKey;
   baseMint: web3.PublicKey;
   spriteMint: web3.PublicKey;
+  spriteMetadata: web3.PublicKey;
   spriteMintSrc: web3.PublicKey;
   spriteMintDst: web3.PublicKey;
   payer: web3.PublicKey;
@@ -60,6 +64,8 @@ export type StoreSpriteInstructionAccounts = {
   systemProgram?: web3.PublicKey;
   splToken: web3.PublicKey;
   splAssociatedToken: web3.PublicKey;
+  config: web3.PublicKey;
+  treasury: web3.PublicKey;
 };
 
 export const storeSpriteInstructionDiscriminator = 1;
@@ -99,6 +105,11 @@ export function createStoreSpriteInstruction(
       isWritable: false,
       isSigner: false,
     },
+    {
+      pubkey: accounts.spriteMetadata,
+      isWritable: false,
+      isSigner: false,
+    },
     {
       pubkey: accounts.spriteMintSrc,
       isWritable: false,
@@ -134,6 +145,16 @@ export function createStoreSpriteInstruction(
       isWritable: false,
       isSigner: false,
     },
+    {
+      pubkey: accounts.config,
+      isWritable: false,
+      isSigner: false,
+    },
+    {
+      pubkey: accounts.treasury,
+      isWritable: true,
+      isSigner: false,
+    },
   ];
 
   const ix = new web3.TransactionInstruction({
@@ -1,2 +1,11 @@
+export * from './AcceptAdmin';
 export * from './CreateSpriteAccount';
+export * from './EquipSprite';
+export * from './InitializeConfig';
+export * from './MigrateSpriteAccount';
+export * from './ProposeAdmin';
+export * from './SetActiveVariant';
+export * from './SetPaletteVariant';
 export * from './StoreSprite';
+export * from './UnequipSprite';
+export * from './UpdateConfig';
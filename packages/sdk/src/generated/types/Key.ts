@@ -12,6 +12,8 @@ import * as beet from '@metaplex-foundation/beet';
  */
 export enum Key {
   Uninitialized,
+  SpriteAccountV1,
+  ProgramConfig,
   SpriteAccount,
 }
 
@@ -10,13 +10,32 @@ import * as beet from '@metaplex-foundation/beet';
 import * as beetSolana from '@metaplex-foundation/beet-solana';
 import { PerspectiveTags, perspectiveTagsBeet } from './PerspectiveTags';
 import { StyleTags, styleTagsBeet } from './StyleTags';
+import { AttributeValue, attributeValueBeet } from './AttributeValue';
+import { EquipmentSlot, equipmentSlotBeet } from './EquipmentSlot';
+import { Palette, paletteBeet } from './Palette';
+import { License, licenseBeet } from './License';
+import { Rendition, renditionBeet } from './Rendition';
+import { Timestamp, timestampBeet } from './Timestamp';
 export type Sprite = {
   name: string;
   description: string;
   perspectiveTags: PerspectiveTags[];
   styleTags: StyleTags[];
   customTags: string[];
+  attributes: [string, AttributeValue][];
   mint: web3.PublicKey;
+  slot: EquipmentSlot;
+  zOrder: number;
+  equipped: boolean;
+  palette: beet.COption<Palette>;
+  paletteVariant: beet.COption<string>;
+  artist: web3.PublicKey;
+  license: License;
+  attribution: beet.COption<string>;
+  family: beet.COption<web3.PublicKey>;
+  renditions: Rendition[];
+  createdAt: Timestamp;
+  updatedAt: Timestamp;
 };
 
 /**
@@ -30,7 +49,20 @@ export const spriteBeet = new beet.FixableBeetArgsStruct<Sprite>(
     ['perspectiveTags', beet.array(perspectiveTagsBeet)],
     ['styleTags', beet.array(styleTagsBeet)],
     ['customTags', beet.array(beet.utf8String)],
+    ['attributes', beet.array(beet.tuple([beet.utf8String, attributeValueBeet]))],
     ['mint', beetSolana.publicKey],
+    ['slot', equipmentSlotBeet],
+    ['zOrder', beet.u8],
+    ['equipped', beet.bool],
+    ['palette', beet.coption(paletteBeet)],
+    ['paletteVariant', beet.coption(beet.utf8String)],
+    ['artist', beetSolana.publicKey],
+    ['license', licenseBeet],
+    ['attribution', beet.coption(beet.utf8String)],
+    ['family', beet.coption(beetSolana.publicKey)],
+    ['renditions', beet.array(renditionBeet)],
+    ['createdAt', timestampBeet],
+    ['updatedAt', timestampBeet],
   ],
   'Sprite',
 );
@@ -5,15 +5,30 @@
  * See: https://github.com/metaplex-foundation/solita
  */
 
+import * as web3 from '@solana/web3.js';
 import * as beet from '@metaplex-foundation/beet';
+import * as beetSolana from '@metaplex-foundation/beet-solana';
 import { PerspectiveTags, perspectiveTagsBeet } from './PerspectiveTags';
 import { StyleTags, styleTagsBeet } from './StyleTags';
+import { AttributeValue, attributeValueBeet } from './AttributeValue';
+import { EquipmentSlot, equipmentSlotBeet } from './EquipmentSlot';
+import { Palette, paletteBeet } from './Palette';
+import { License, licenseBeet } from './License';
+import { Rendition, renditionBeet } from './Rendition';
 export type StoreSpriteArgs = {
   name: string;
   description: string;
   perspectiveTags: PerspectiveTags[];
   styleTags: StyleTags[];
   customTags: string[];
+  attributes: [string, AttributeValue][];
+  slot: EquipmentSlot;
+  zOrder: number;
+  palette: beet.COption<Palette>;
+  license: License;
+  attribution: beet.COption<string>;
+  family: beet.COption<web3.PublicKey>;
+  renditions: Rendition[];
 };
 
 /**
@@ -27,6 +42,14 @@ export const storeSpriteArgsBeet = new beet.FixableBeetArgsStruct<StoreSpriteArg
     ['perspectiveTags', beet.array(perspectiveTagsBeet)],
     ['styleTags', beet.array(styleTagsBeet)],
     ['customTags', beet.array(beet.utf8String)],
+    ['attributes', beet.array(beet.tuple([beet.utf8String, attributeValueBeet]))],
+    ['slot', equipmentSlotBeet],
+    ['zOrder', beet.u8],
+    ['palette', beet.coption(paletteBeet)],
+    ['license', licenseBeet],
+    ['attribution', beet.coption(beet.utf8String)],
+    ['family', beet.coption(beetSolana.publicKey)],
+    ['renditions', beet.array(renditionBeet)],
   ],
   'StoreSpriteArgs',
 );
@@ -1,5 +1,23 @@
+export * from './AttributeValue';
+export * from './ConfigLimits';
+export * from './EquipmentSlot';
+export * from './FeeSchedule';
+export * from './InitializeConfigArgs';
 export * from './Key';
+export * from './License';
+export * from './Palette';
+export * from './PaletteVariant';
 export * from './PerspectiveTags';
+export * from './ProposeAdminArgs';
+export * from './Rendition';
+export * from './RenditionFormat';
+export * from './Rgba';
+export * from './SetPaletteVariantArgs';
 export * from './Sprite';
+export * from './SpriteEvent';
+export * from './SpriteFamily';
 export * from './StoreSpriteArgs';
 export * from './StyleTags';
+export * from './Timestamp';
+export * from './TokenFee';
+export * from './UpdateConfigArgs';
@@ -0,0 +1,306 @@
+//! Instruction builders that resolve every derived account from the base mint, sprite mint and
+//! owner, so callers don't have to line up the positional arguments of [`crate::instruction`].
+//!
+//! ```ignore
+//! let instruction = StoreSpriteBuilder::new(base_mint, sprite_mint, owner, args)
+//!     .fees(&config.fees)
+//!     .instruction();
+//! ```
+
+use mpl_token_metadata::pda::{find_master_edition_account, find_metadata_account};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::{
+    instruction::{
+        create_sprite_account, equip_sprite, set_active_variant, set_palette_variant, store_sprite,
+        unequip_sprite, StoreSpriteArgs,
+    },
+    pda::{find_escrow_address, find_sprite_address},
+    state::FeeSchedule,
+};
+
+/// Builds `CreateSpriteAccount` for a base token held in the owner's associated token account.
+#[derive(Clone, Debug)]
+pub struct CreateSpriteAccountBuilder {
+    base_mint: Pubkey,
+    owner: Pubkey,
+    base_token_account: Option<Pubkey>,
+}
+
+impl CreateSpriteAccountBuilder {
+    pub fn new(base_mint: Pubkey, owner: Pubkey) -> Self {
+        Self {
+            base_mint,
+            owner,
+            base_token_account: None,
+        }
+    }
+
+    /// Overrides the token account holding the base token, the owner's ATA by default.
+    pub fn base_token_account(mut self, base_token_account: Pubkey) -> Self {
+        self.base_token_account = Some(base_token_account);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (escrow, _) = find_escrow_address(&self.base_mint);
+        let (sprite_account, _) = find_sprite_address(&self.base_mint);
+        let (metadata, _) = find_metadata_account(&self.base_mint);
+        let (edition, _) = find_master_edition_account(&self.base_mint);
+        let base_token_account = self
+            .base_token_account
+            .unwrap_or_else(|| get_associated_token_address(&self.owner, &self.base_mint));
+
+        create_sprite_account(
+            &crate::id(),
+            &escrow,
+            &metadata,
+            &self.base_mint,
+            &base_token_account,
+            &edition,
+            &sprite_account,
+            &self.owner,
+        )
+    }
+}
+
+/// Builds `StoreSprite`, moving the sprite token from the owner's associated token account into
+/// the escrow's. Without [`StoreSpriteBuilder::fees`] no fee accounts are passed, which is only
+/// accepted when the program charges no fee or the owner is exempt.
+#[derive(Clone, Debug)]
+pub struct StoreSpriteBuilder {
+    base_mint: Pubkey,
+    sprite_mint: Pubkey,
+    owner: Pubkey,
+    args: StoreSpriteArgs,
+    sprite_token_account: Option<Pubkey>,
+    treasury: Option<Pubkey>,
+    fee_token_accounts: Option<(Pubkey, Pubkey)>,
+}
+
+impl StoreSpriteBuilder {
+    pub fn new(
+        base_mint: Pubkey,
+        sprite_mint: Pubkey,
+        owner: Pubkey,
+        args: StoreSpriteArgs,
+    ) -> Self {
+        Self {
+            base_mint,
+            sprite_mint,
+            owner,
+            args,
+            sprite_token_account: None,
+            treasury: None,
+            fee_token_accounts: None,
+        }
+    }
+
+    /// Overrides the token account the sprite is taken from, the owner's ATA by default.
+    pub fn sprite_token_account(mut self, sprite_token_account: Pubkey) -> Self {
+        self.sprite_token_account = Some(sprite_token_account);
+        self
+    }
+
+    /// Resolves the treasury and, for a token fee, the owner's and treasury's associated token
+    /// accounts for the fee mint from the program config's fee schedule.
+    pub fn fees(mut self, fees: &FeeSchedule) -> Self {
+        self.treasury = Some(fees.treasury);
+        self.fee_token_accounts = fees.token_fee.map(|token_fee| {
+            (
+                get_associated_token_address(&self.owner, &token_fee.mint),
+                get_associated_token_address(&fees.treasury, &token_fee.mint),
+            )
+        });
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (escrow, _) = find_escrow_address(&self.base_mint);
+        let (sprite_account, _) = find_sprite_address(&self.base_mint);
+        let (sprite_metadata, _) = find_metadata_account(&self.sprite_mint);
+        let sprite_token_account = self
+            .sprite_token_account
+            .unwrap_or_else(|| get_associated_token_address(&self.owner, &self.sprite_mint));
+        store_sprite(
+            &crate::id(),
+            &escrow,
+            &self.base_mint,
+            &self.sprite_mint,
+            &sprite_metadata,
+            &sprite_token_account,
+            &get_associated_token_address(&escrow, &self.sprite_mint),
+            &self.owner,
+            &sprite_account,
+            &self.treasury.unwrap_or(self.owner),
+            self.fee_token_accounts,
+            self.args.clone(),
+        )
+    }
+}
+
+/// Accounts of the instructions a base token holder runs against one of its stored sprites.
+#[derive(Clone, Debug)]
+struct HolderAccounts {
+    base_mint: Pubkey,
+    sprite_mint: Pubkey,
+    owner: Pubkey,
+    base_token_account: Option<Pubkey>,
+}
+
+impl HolderAccounts {
+    fn new(base_mint: Pubkey, sprite_mint: Pubkey, owner: Pubkey) -> Self {
+        Self {
+            base_mint,
+            sprite_mint,
+            owner,
+            base_token_account: None,
+        }
+    }
+
+    /// Returns the sprite account and the token account holding the base token.
+    fn resolve(&self) -> (Pubkey, Pubkey) {
+        let (sprite_account, _) = find_sprite_address(&self.base_mint);
+        let base_token_account = self
+            .base_token_account
+            .unwrap_or_else(|| get_associated_token_address(&self.owner, &self.base_mint));
+        (sprite_account, base_token_account)
+    }
+}
+
+/// Builds `EquipSprite` for a sprite stored under the owner's base token.
+#[derive(Clone, Debug)]
+pub struct EquipSpriteBuilder {
+    accounts: HolderAccounts,
+}
+
+impl EquipSpriteBuilder {
+    pub fn new(base_mint: Pubkey, sprite_mint: Pubkey, owner: Pubkey) -> Self {
+        Self {
+            accounts: HolderAccounts::new(base_mint, sprite_mint, owner),
+        }
+    }
+
+    /// Overrides the token account holding the base token, the owner's ATA by default.
+    pub fn base_token_account(mut self, base_token_account: Pubkey) -> Self {
+        self.accounts.base_token_account = Some(base_token_account);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (sprite_account, base_token_account) = self.accounts.resolve();
+        equip_sprite(
+            &crate::id(),
+            &sprite_account,
+            &self.accounts.base_mint,
+            &base_token_account,
+            &self.accounts.sprite_mint,
+            &self.accounts.owner,
+        )
+    }
+}
+
+/// Builds `UnequipSprite` for a sprite stored under the owner's base token.
+#[derive(Clone, Debug)]
+pub struct UnequipSpriteBuilder {
+    accounts: HolderAccounts,
+}
+
+impl UnequipSpriteBuilder {
+    pub fn new(base_mint: Pubkey, sprite_mint: Pubkey, owner: Pubkey) -> Self {
+        Self {
+            accounts: HolderAccounts::new(base_mint, sprite_mint, owner),
+        }
+    }
+
+    /// Overrides the token account holding the base token, the owner's ATA by default.
+    pub fn base_token_account(mut self, base_token_account: Pubkey) -> Self {
+        self.accounts.base_token_account = Some(base_token_account);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (sprite_account, base_token_account) = self.accounts.resolve();
+        unequip_sprite(
+            &crate::id(),
+            &sprite_account,
+            &self.accounts.base_mint,
+            &base_token_account,
+            &self.accounts.sprite_mint,
+            &self.accounts.owner,
+        )
+    }
+}
+
+/// Builds `SetPaletteVariant`, selecting `variant` or the base palette when `None`.
+#[derive(Clone, Debug)]
+pub struct SetPaletteVariantBuilder {
+    accounts: HolderAccounts,
+    variant: Option<String>,
+}
+
+impl SetPaletteVariantBuilder {
+    pub fn new(
+        base_mint: Pubkey,
+        sprite_mint: Pubkey,
+        owner: Pubkey,
+        variant: Option<String>,
+    ) -> Self {
+        Self {
+            accounts: HolderAccounts::new(base_mint, sprite_mint, owner),
+            variant,
+        }
+    }
+
+    /// Overrides the token account holding the base token, the owner's ATA by default.
+    pub fn base_token_account(mut self, base_token_account: Pubkey) -> Self {
+        self.accounts.base_token_account = Some(base_token_account);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (sprite_account, base_token_account) = self.accounts.resolve();
+        set_palette_variant(
+            &crate::id(),
+            &sprite_account,
+            &self.accounts.base_mint,
+            &base_token_account,
+            &self.accounts.sprite_mint,
+            &self.accounts.owner,
+            self.variant.clone(),
+        )
+    }
+}
+
+/// Builds `SetActiveVariant`, showing the given sprite in place of the rest of its family.
+#[derive(Clone, Debug)]
+pub struct SetActiveVariantBuilder {
+    accounts: HolderAccounts,
+}
+
+impl SetActiveVariantBuilder {
+    pub fn new(base_mint: Pubkey, sprite_mint: Pubkey, owner: Pubkey) -> Self {
+        Self {
+            accounts: HolderAccounts::new(base_mint, sprite_mint, owner),
+        }
+    }
+
+    /// Overrides the token account holding the base token, the owner's ATA by default.
+    pub fn base_token_account(mut self, base_token_account: Pubkey) -> Self {
+        self.accounts.base_token_account = Some(base_token_account);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (sprite_account, base_token_account) = self.accounts.resolve();
+        set_active_variant(
+            &crate::id(),
+            &sprite_account,
+            &self.accounts.base_mint,
+            &base_token_account,
+            &self.accounts.sprite_mint,
+            &self.accounts.owner,
+        )
+    }
+}
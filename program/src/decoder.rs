@@ -0,0 +1,279 @@
+//! Decodes sprite manager instructions together with their accounts, for explorers and wallets.
+
+use std::fmt;
+
+use borsh::BorshDeserialize;
+use solana_program::{
+    instruction::CompiledInstruction, program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::{instruction::SpriteManagerInstruction, state::AttributeValue};
+
+/// Names of the fee token accounts that follow `StoreSprite`'s named accounts when the config
+/// charges a token fee.
+const STORE_SPRITE_FEE_ACCOUNTS: &[&str] = &["fee_token_src", "fee_token_dst"];
+
+const HOLDER_ACCOUNTS: &[&str] = &[
+    "sprite_pda",
+    "base_mint",
+    "base_token_account",
+    "sprite_mint",
+    "owner",
+    "config",
+];
+
+/// Returns the account names from the instruction's shank `#[account(...)]` annotations, in order.
+pub fn account_names(instruction: &SpriteManagerInstruction) -> &'static [&'static str] {
+    match instruction {
+        SpriteManagerInstruction::CreateSpriteAccount => &[
+            "escrow",
+            "metadata",
+            "mint",
+            "token_account",
+            "edition",
+            "sprite_pda",
+            "creator",
+            "token_metadata_program",
+            "system_program",
+            "sysvar_instructions",
+            "config",
+        ],
+        SpriteManagerInstruction::StoreSprite(_) => &[
+            "escrow",
+            "base_mint",
+            "sprite_mint",
+            "sprite_metadata",
+            "sprite_mint_src",
+            "sprite_mint_dst",
+            "payer",
+            "sprite_pda",
+            "system_program",
+            "spl_token",
+            "spl_associated_token",
+            "config",
+            "treasury",
+        ],
+        SpriteManagerInstruction::EquipSprite
+        | SpriteManagerInstruction::UnequipSprite
+        | SpriteManagerInstruction::SetActiveVariant => HOLDER_ACCOUNTS,
+        SpriteManagerInstruction::SetPaletteVariant(_) => &[
+            "sprite_pda",
+            "base_mint",
+            "base_token_account",
+            "sprite_mint",
+            "owner",
+            "system_program",
+            "config",
+        ],
+        SpriteManagerInstruction::InitializeConfig(_) => {
+            &["config", "admin", "system_program", "program_data"]
+        }
+        SpriteManagerInstruction::UpdateConfig(_) | SpriteManagerInstruction::ProposeAdmin(_) => {
+            &["config", "admin"]
+        }
+        SpriteManagerInstruction::AcceptAdmin => &["config", "new_admin"],
+        SpriteManagerInstruction::MigrateSpriteAccount => {
+            &["sprite_pda", "base_mint", "payer", "system_program"]
+        }
+    }
+}
+
+/// The name of the instruction variant, e.g. `StoreSprite`.
+pub fn instruction_name(instruction: &SpriteManagerInstruction) -> &'static str {
+    match instruction {
+        SpriteManagerInstruction::CreateSpriteAccount => "CreateSpriteAccount",
+        SpriteManagerInstruction::StoreSprite(_) => "StoreSprite",
+        SpriteManagerInstruction::EquipSprite => "EquipSprite",
+        SpriteManagerInstruction::UnequipSprite => "UnequipSprite",
+        SpriteManagerInstruction::SetPaletteVariant(_) => "SetPaletteVariant",
+        SpriteManagerInstruction::SetActiveVariant => "SetActiveVariant",
+        SpriteManagerInstruction::InitializeConfig(_) => "InitializeConfig",
+        SpriteManagerInstruction::UpdateConfig(_) => "UpdateConfig",
+        SpriteManagerInstruction::ProposeAdmin(_) => "ProposeAdmin",
+        SpriteManagerInstruction::AcceptAdmin => "AcceptAdmin",
+        SpriteManagerInstruction::MigrateSpriteAccount => "MigrateSpriteAccount",
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedAccount {
+    /// The shank account name, or `remaining` for accounts past the named ones.
+    pub name: &'static str,
+    pub pubkey: Pubkey,
+}
+
+#[derive(Clone, Debug)]
+pub struct DecodedInstruction {
+    pub instruction: SpriteManagerInstruction,
+    pub accounts: Vec<DecodedAccount>,
+}
+
+impl DecodedInstruction {
+    pub fn name(&self) -> &'static str {
+        instruction_name(&self.instruction)
+    }
+
+    /// Looks up an account by its shank name.
+    pub fn account(&self, name: &str) -> Option<&Pubkey> {
+        self.accounts
+            .iter()
+            .find(|account| account.name == name)
+            .map(|account| &account.pubkey)
+    }
+}
+
+/// Decodes instruction data and the instruction's account keys, in instruction order.
+///
+/// Fails with `InvalidInstructionData` when the data is not a `SpriteManagerInstruction` and with
+/// `NotEnoughAccountKeys` when fewer accounts are given than the instruction names.
+pub fn decode_instruction(
+    data: &[u8],
+    accounts: &[Pubkey],
+) -> Result<DecodedInstruction, ProgramError> {
+    let instruction = SpriteManagerInstruction::try_from_slice(data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let names = account_names(&instruction);
+    if accounts.len() < names.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let extra_names: &[&str] = match instruction {
+        SpriteManagerInstruction::StoreSprite(_) => STORE_SPRITE_FEE_ACCOUNTS,
+        _ => &[],
+    };
+
+    let accounts = accounts
+        .iter()
+        .enumerate()
+        .map(|(index, pubkey)| DecodedAccount {
+            name: names
+                .iter()
+                .chain(extra_names)
+                .nth(index)
+                .copied()
+                .unwrap_or("remaining"),
+            pubkey: *pubkey,
+        })
+        .collect();
+
+    Ok(DecodedInstruction {
+        instruction,
+        accounts,
+    })
+}
+
+/// Decodes a compiled instruction against the account keys of the message it belongs to.
+pub fn decode_compiled_instruction(
+    instruction: &CompiledInstruction,
+    account_keys: &[Pubkey],
+) -> Result<DecodedInstruction, ProgramError> {
+    let key = |index: u8| {
+        account_keys
+            .get(index as usize)
+            .copied()
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+
+    if key(instruction.program_id_index)? != crate::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|index| key(*index))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    decode_instruction(&instruction.data, &accounts)
+}
+
+fn attribute_value(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::String(value) => value.clone(),
+        AttributeValue::Integer(value) => value.to_string(),
+        AttributeValue::Bool(value) => value.to_string(),
+    }
+}
+
+/// A multi-line summary for a wallet's confirmation screen: the instruction, its arguments and
+/// its named accounts.
+impl fmt::Display for DecodedInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Sprite Manager: {}", self.name())?;
+
+        match &self.instruction {
+            SpriteManagerInstruction::StoreSprite(args) => {
+                writeln!(f, "  Name: {}", args.name)?;
+                if !args.description.is_empty() {
+                    writeln!(f, "  Description: {}", args.description)?;
+                }
+                writeln!(f, "  Slot: {:?} (z-order {})", args.slot, args.z_order)?;
+                if !args.perspective_tags.is_empty() {
+                    writeln!(f, "  Perspectives: {:?}", args.perspective_tags)?;
+                }
+                if !args.style_tags.is_empty() {
+                    writeln!(f, "  Styles: {:?}", args.style_tags)?;
+                }
+                if !args.custom_tags.is_empty() {
+                    writeln!(f, "  Tags: {}", args.custom_tags.join(", "))?;
+                }
+                for (key, value) in &args.attributes {
+                    writeln!(f, "  {}: {}", key, attribute_value(value))?;
+                }
+                if let Some(palette) = &args.palette {
+                    writeln!(
+                        f,
+                        "  Palette: {} colors, {} variants",
+                        palette.colors.len(),
+                        palette.variants.len()
+                    )?;
+                }
+                writeln!(f, "  License: {}", args.license)?;
+                if let Some(attribution) = &args.attribution {
+                    writeln!(f, "  Attribution: {}", attribution)?;
+                }
+                if let Some(family) = &args.family {
+                    writeln!(f, "  Family: {}", family)?;
+                }
+                if !args.renditions.is_empty() {
+                    writeln!(f, "  Renditions: {}", args.renditions.len())?;
+                }
+            }
+            SpriteManagerInstruction::SetPaletteVariant(args) => writeln!(
+                f,
+                "  Variant: {}",
+                args.variant.as_deref().unwrap_or("base palette")
+            )?,
+            SpriteManagerInstruction::InitializeConfig(args) => {
+                writeln!(f, "  Limits: {:?}", args.limits)?;
+                writeln!(f, "  Fees: {:?}", args.fees)?;
+            }
+            SpriteManagerInstruction::UpdateConfig(args) => {
+                if let Some(paused) = args.paused {
+                    writeln!(f, "  Paused: {}", paused)?;
+                }
+                if let Some(limits) = &args.limits {
+                    writeln!(f, "  Limits: {:?}", limits)?;
+                }
+                if let Some(fees) = &args.fees {
+                    writeln!(f, "  Fees: {:?}", fees)?;
+                }
+            }
+            SpriteManagerInstruction::ProposeAdmin(args) => match &args.new_admin {
+                Some(new_admin) => writeln!(f, "  New admin: {}", new_admin)?,
+                None => writeln!(f, "  Cancel the pending admin")?,
+            },
+            SpriteManagerInstruction::CreateSpriteAccount
+            | SpriteManagerInstruction::EquipSprite
+            | SpriteManagerInstruction::UnequipSprite
+            | SpriteManagerInstruction::SetActiveVariant
+            | SpriteManagerInstruction::AcceptAdmin
+            | SpriteManagerInstruction::MigrateSpriteAccount => {}
+        }
+
+        write!(f, "Accounts:")?;
+        for account in &self.accounts {
+            write!(f, "\n  {}: {}", account.name, account.pubkey)?;
+        }
+        Ok(())
+    }
+}
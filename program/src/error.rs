@@ -35,6 +35,102 @@ pub enum SpriteManagerError {
     /// 6 - NumericalOverflowError
     #[error("NumericalOverflowError")]
     NumericalOverflow,
+
+    /// 7 - Sprite not found
+    #[error("Sprite not found")]
+    SpriteNotFound,
+
+    /// 8 - Equipment slot already occupied
+    #[error("Equipment slot already occupied")]
+    SlotOccupied,
+
+    /// 9 - Sprite is not equipped
+    #[error("Sprite is not equipped")]
+    SpriteNotEquipped,
+
+    /// 10 - Signer does not hold the base token
+    #[error("Signer does not hold the base token")]
+    NotBaseTokenHolder,
+
+    /// 11 - Invalid palette
+    #[error("Invalid palette")]
+    InvalidPalette,
+
+    /// 12 - Palette variant not found
+    #[error("Palette variant not found")]
+    PaletteVariantNotFound,
+
+    /// 13 - Sprite metadata has no verified creator
+    #[error("Sprite metadata has no verified creator")]
+    NoVerifiedCreator,
+
+    /// 14 - Metadata does not match the sprite mint
+    #[error("Metadata does not match the sprite mint")]
+    MetadataMintMismatch,
+
+    /// 15 - Invalid sprite attributes
+    #[error("Invalid sprite attributes")]
+    InvalidAttributes,
+
+    /// 16 - Duplicate attribute key
+    #[error("Duplicate attribute key")]
+    DuplicateAttributeKey,
+
+    /// 17 - Sprite does not belong to a family
+    #[error("Sprite does not belong to a family")]
+    SpriteHasNoFamily,
+
+    /// 18 - Invalid rendition
+    #[error("Invalid rendition")]
+    InvalidRendition,
+
+    /// 19 - Program is paused
+    #[error("Program is paused")]
+    ProgramPaused,
+
+    /// 20 - Signer is not the program admin
+    #[error("Signer is not the program admin")]
+    InvalidAdmin,
+
+    /// 21 - Signer is not the pending admin
+    #[error("Signer is not the pending admin")]
+    NotPendingAdmin,
+
+    /// 22 - Program config already initialized
+    #[error("Program config already initialized")]
+    ConfigAlreadyInitialized,
+
+    /// 23 - Configured size limit exceeded
+    #[error("Configured size limit exceeded")]
+    LimitExceeded,
+
+    /// 24 - Invalid treasury account
+    #[error("Invalid treasury account")]
+    InvalidTreasury,
+
+    /// 25 - Invalid sprite token account
+    #[error("Invalid sprite token account")]
+    InvalidTokenAccount,
+
+    /// 26 - Sprite is not the active variant of its family
+    #[error("Sprite is not the active variant of its family")]
+    VariantNotActive,
+
+    /// 27 - Signer is not the program upgrade authority
+    #[error("Signer is not the program upgrade authority")]
+    NotUpgradeAuthority,
+
+    /// 28 - Sprite is already stored
+    #[error("Sprite is already stored")]
+    SpriteAlreadyStored,
+
+    /// 29 - Sprite account uses the original layout and must be migrated
+    #[error("Sprite account uses the original layout and must be migrated")]
+    SpriteAccountNotMigrated,
+
+    /// 30 - Sprite account already uses the current layout
+    #[error("Sprite account already uses the current layout")]
+    SpriteAccountAlreadyMigrated,
 }
 
 impl PrintProgramError for SpriteManagerError {
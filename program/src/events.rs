@@ -0,0 +1,105 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+use crate::state::{EquipmentSlot, TokenFee};
+
+/// Events logged with `sol_log_data` so indexers can follow changes without diffing accounts.
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+pub enum SpriteEvent {
+    SpriteAccountCreated {
+        base_mint: Pubkey,
+        sprite_account: Pubkey,
+    },
+    SpriteStored {
+        base_mint: Pubkey,
+        sprite_mint: Pubkey,
+        slot: EquipmentSlot,
+    },
+    SpriteEquipped {
+        base_mint: Pubkey,
+        sprite_mint: Pubkey,
+    },
+    SpriteUnequipped {
+        base_mint: Pubkey,
+        sprite_mint: Pubkey,
+    },
+    /// A stored sprite's display settings, such as its palette variant, changed.
+    SpriteUpdated {
+        base_mint: Pubkey,
+        sprite_mint: Pubkey,
+    },
+    ActiveVariantChanged {
+        base_mint: Pubkey,
+        family: Pubkey,
+        previous_variant: Pubkey,
+        active_variant: Pubkey,
+    },
+    FeeCharged {
+        payer: Pubkey,
+        treasury: Pubkey,
+        lamports: u64,
+        token_fee: Option<TokenFee>,
+    },
+    ConfigInitialized {
+        admin: Pubkey,
+    },
+    ConfigUpdated {
+        admin: Pubkey,
+    },
+    AdminProposed {
+        admin: Pubkey,
+        pending_admin: Option<Pubkey>,
+    },
+    AdminChanged {
+        previous_admin: Pubkey,
+        admin: Pubkey,
+    },
+}
+
+impl SpriteEvent {
+    pub fn emit(&self) {
+        if let Ok(data) = self.try_to_vec() {
+            sol_log_data(&[&data]);
+        }
+    }
+}
+
+/// Decodes the events this program logged from a transaction's log messages.
+///
+/// Invocations are tracked through the `invoke`/`success`/`failed` lines so data logged by other
+/// programs, including ones called through CPI, is skipped. Lines that do not decode are ignored.
+#[cfg(not(target_os = "solana"))]
+pub fn parse_events<S: AsRef<str>>(log_messages: &[S]) -> Vec<SpriteEvent> {
+    const PROGRAM_DATA: &str = "Program data: ";
+
+    let program_id = crate::id().to_string();
+    let mut invoke_stack: Vec<bool> = vec![];
+    let mut events = vec![];
+
+    for message in log_messages {
+        let message = message.as_ref();
+        if let Some(data) = message.strip_prefix(PROGRAM_DATA) {
+            if invoke_stack.last() != Some(&true) {
+                continue;
+            }
+            let event = data
+                .split(' ')
+                .next()
+                .and_then(|field| base64::decode(field).ok())
+                .and_then(|bytes| SpriteEvent::try_from_slice(&bytes).ok());
+            if let Some(event) = event {
+                events.push(event);
+            }
+        } else if let Some(rest) = message.strip_prefix("Program ") {
+            let mut parts = rest.splitn(2, ' ');
+            let (id, status) = (parts.next(), parts.next().unwrap_or_default());
+            if status.starts_with("invoke [") {
+                invoke_stack.push(id == Some(program_id.as_str()));
+            } else if status == "success" || status.starts_with("failed") {
+                invoke_stack.pop();
+            }
+        }
+    }
+
+    events
+}
@@ -6,19 +6,66 @@ use solana_program::{
     sysvar,
 };
 
-use crate::state::{PerspectiveTags, StyleTags};
+use crate::{
+    pda::{find_config_address, find_program_data_address},
+    state::{
+        AttributeValue, ConfigLimits, EquipmentSlot, FeeSchedule, License, Palette,
+        PerspectiveTags, Rendition, StyleTags,
+    },
+};
 
 #[repr(C)]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Default)]
 pub struct StoreSpriteArgs {
     pub name: String,
     pub description: String,
     pub perspective_tags: Vec<PerspectiveTags>,
     pub style_tags: Vec<StyleTags>,
     pub custom_tags: Vec<String>,
+    pub attributes: Vec<(String, AttributeValue)>,
+    pub slot: EquipmentSlot,
+    pub z_order: u8,
+    pub palette: Option<Palette>,
+    pub license: License,
+    pub attribution: Option<String>,
+    pub family: Option<Pubkey>,
+    pub renditions: Vec<Rendition>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetPaletteVariantArgs {
+    /// The name of the palette variant to use, `None` resets to the base palette.
+    pub variant: Option<String>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct InitializeConfigArgs {
+    pub limits: ConfigLimits,
+    pub fees: FeeSchedule,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct UpdateConfigArgs {
+    /// Leaves the paused flag unchanged when `None`.
+    pub paused: Option<bool>,
+    /// Leaves the limits unchanged when `None`.
+    pub limits: Option<ConfigLimits>,
+    /// Leaves the fee schedule unchanged when `None`.
+    pub fees: Option<FeeSchedule>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ProposeAdminArgs {
+    /// The admin to hand over to, `None` cancels a pending proposal.
+    pub new_admin: Option<Pubkey>,
 }
 
 #[derive(Debug, Clone, ShankInstruction, BorshSerialize, BorshDeserialize)]
+#[allow(clippy::large_enum_variant)]
 #[rustfmt::skip]
 pub enum SpriteManagerInstruction {
     /// Instruction for creating a sprite storage account
@@ -32,20 +79,95 @@ pub enum SpriteManagerInstruction {
     #[account(7, name = "token_metadata_program", desc = "Token Metadata program")]
     #[account(8, name = "system_program", desc = "System program")]
     #[account(9, name="sysvar_instructions", desc="Instructions sysvar account")]
+    #[account(10, name = "config", desc = "Program config PDA")]
     CreateSpriteAccount,
 
     /// Instruction for storing a sprite
+    ///
+    /// When the config charges a token fee, the payer's token account and the treasury's token
+    /// account for the fee mint follow as two additional writable accounts.
     #[account(0, writable, name = "escrow", desc = "Escrow account")]
     #[account(1, name = "base_mint", desc = "Mint account of the base token")]
     #[account(2, name = "sprite_mint", desc = "Mint account of the sprite token")]
-    #[account(3, name = "sprite_mint_src", desc = "Source account of the sprite token")]
-    #[account(4, name = "sprite_mint_dst", desc = "Destination account of the sprite token")]
-    #[account(5, writable, signer, name="payer", desc="The creator of the account and manager of the sprite")]
-    #[account(6, writable, name="sprite_pda", desc = "The PDA for sprite data")]
-    #[account(7, name = "system_program", desc = "System program")]
-    #[account(8, name = "spl_token", desc = "Token program")]
-    #[account(9, name = "spl_associated_token", desc = "Associated token account program")]
+    #[account(3, name = "sprite_metadata", desc = "Metadata account of the sprite token")]
+    #[account(4, name = "sprite_mint_src", desc = "Source account of the sprite token")]
+    #[account(5, name = "sprite_mint_dst", desc = "Destination account of the sprite token")]
+    #[account(6, writable, signer, name="payer", desc="The creator of the account and manager of the sprite")]
+    #[account(7, writable, name="sprite_pda", desc = "The PDA for sprite data")]
+    #[account(8, name = "system_program", desc = "System program")]
+    #[account(9, name = "spl_token", desc = "Token program")]
+    #[account(10, name = "spl_associated_token", desc = "Associated token account program")]
+    #[account(11, name = "config", desc = "Program config PDA")]
+    #[account(12, writable, name = "treasury", desc = "Treasury receiving the store fee")]
     StoreSprite(StoreSpriteArgs),
+
+    /// Instruction for equipping a stored sprite into its slot
+    #[account(0, writable, name = "sprite_pda", desc = "The PDA for sprite data")]
+    #[account(1, name = "base_mint", desc = "Mint account of the base token")]
+    #[account(2, name = "base_token_account", desc = "Token account holding the base token")]
+    #[account(3, name = "sprite_mint", desc = "Mint account of the sprite token")]
+    #[account(4, signer, name = "owner", desc = "The holder of the base token")]
+    #[account(5, name = "config", desc = "Program config PDA")]
+    EquipSprite,
+
+    /// Instruction for removing an equipped sprite from its slot
+    #[account(0, writable, name = "sprite_pda", desc = "The PDA for sprite data")]
+    #[account(1, name = "base_mint", desc = "Mint account of the base token")]
+    #[account(2, name = "base_token_account", desc = "Token account holding the base token")]
+    #[account(3, name = "sprite_mint", desc = "Mint account of the sprite token")]
+    #[account(4, signer, name = "owner", desc = "The holder of the base token")]
+    #[account(5, name = "config", desc = "Program config PDA")]
+    UnequipSprite,
+
+    /// Instruction for selecting the palette variant a sprite is drawn with
+    #[account(0, writable, name = "sprite_pda", desc = "The PDA for sprite data")]
+    #[account(1, name = "base_mint", desc = "Mint account of the base token")]
+    #[account(2, name = "base_token_account", desc = "Token account holding the base token")]
+    #[account(3, name = "sprite_mint", desc = "Mint account of the sprite token")]
+    #[account(4, writable, signer, name = "owner", desc = "The holder of the base token")]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(6, name = "config", desc = "Program config PDA")]
+    SetPaletteVariant(SetPaletteVariantArgs),
+
+    /// Instruction for switching which variant of a sprite family is shown
+    #[account(0, writable, name = "sprite_pda", desc = "The PDA for sprite data")]
+    #[account(1, name = "base_mint", desc = "Mint account of the base token")]
+    #[account(2, name = "base_token_account", desc = "Token account holding the base token")]
+    #[account(3, name = "sprite_mint", desc = "Mint account of the variant to show")]
+    #[account(4, signer, name = "owner", desc = "The holder of the base token")]
+    #[account(5, name = "config", desc = "Program config PDA")]
+    SetActiveVariant,
+
+    /// Instruction for creating the program config, signed by the program's upgrade authority
+    /// which becomes the admin
+    #[account(0, writable, name = "config", desc = "Program config PDA")]
+    #[account(1, writable, signer, name = "admin", desc = "The upgrade authority of the program")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "program_data", desc = "ProgramData account of the program")]
+    InitializeConfig(InitializeConfigArgs),
+
+    /// Instruction for pausing the program or changing its limits
+    #[account(0, writable, name = "config", desc = "Program config PDA")]
+    #[account(1, signer, name = "admin", desc = "The admin of the program config")]
+    UpdateConfig(UpdateConfigArgs),
+
+    /// Instruction for proposing a new admin
+    #[account(0, writable, name = "config", desc = "Program config PDA")]
+    #[account(1, signer, name = "admin", desc = "The admin of the program config")]
+    ProposeAdmin(ProposeAdminArgs),
+
+    /// Instruction for the proposed admin to take over the program config
+    #[account(0, writable, name = "config", desc = "Program config PDA")]
+    #[account(1, signer, name = "new_admin", desc = "The pending admin of the program config")]
+    AcceptAdmin,
+
+    /// Instruction for rewriting a sprite account from the original layout in the current one,
+    /// anyone can pay for it
+    #[account(0, writable, name = "sprite_pda", desc = "The PDA for sprite data")]
+    #[account(1, name = "base_mint", desc = "Mint account of the base token")]
+    #[account(2, writable, signer, name = "payer", desc = "Pays for the larger account")]
+    #[account(3, name = "system_program", desc = "System program")]
+    MigrateSpriteAccount,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -70,6 +192,7 @@ pub fn create_sprite_account(
         AccountMeta::new_readonly(mpl_token_metadata::id(), false),
         AccountMeta::new_readonly(solana_program::system_program::id(), false),
         AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(find_config_address().0, false),
     ];
 
     Instruction {
@@ -87,40 +210,246 @@ pub fn store_sprite(
     escrow: &Pubkey,
     base_mint: &Pubkey,
     sprite_mint: &Pubkey,
+    sprite_metadata: &Pubkey,
     sprite_mint_src: &Pubkey,
     sprite_mint_dst: &Pubkey,
     payer: &Pubkey,
     sprite_account: &Pubkey,
-    name: String,
-    description: String,
-    perspective_tags: Vec<PerspectiveTags>,
-    style_tags: Vec<StyleTags>,
-    custom_tags: Vec<String>,
+    treasury: &Pubkey,
+    fee_token_accounts: Option<(Pubkey, Pubkey)>,
+    args: StoreSpriteArgs,
 ) -> Instruction {
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*escrow, false),
         AccountMeta::new_readonly(*base_mint, false),
         AccountMeta::new_readonly(*sprite_mint, false),
+        AccountMeta::new_readonly(*sprite_metadata, false),
         AccountMeta::new(*sprite_mint_src, false),
         AccountMeta::new(*sprite_mint_dst, false),
-        AccountMeta::new_readonly(*payer, true),
+        AccountMeta::new(*payer, true),
         AccountMeta::new(*sprite_account, false),
         AccountMeta::new_readonly(solana_program::system_program::id(), false),
         AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+        AccountMeta::new(*treasury, false),
+    ];
+
+    if let Some((fee_token_src, fee_token_dst)) = fee_token_accounts {
+        accounts.push(AccountMeta::new(fee_token_src, false));
+        accounts.push(AccountMeta::new(fee_token_dst, false));
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: SpriteManagerInstruction::StoreSprite(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn equip_sprite(
+    program_id: &Pubkey,
+    sprite_account: &Pubkey,
+    base_mint: &Pubkey,
+    base_token_account: &Pubkey,
+    sprite_mint: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*sprite_account, false),
+        AccountMeta::new_readonly(*base_mint, false),
+        AccountMeta::new_readonly(*base_token_account, false),
+        AccountMeta::new_readonly(*sprite_mint, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(find_config_address().0, false),
     ];
 
     Instruction {
         program_id: *program_id,
         accounts,
-        data: SpriteManagerInstruction::StoreSprite(StoreSpriteArgs {
-            name,
-            description,
-            perspective_tags,
-            style_tags,
-            custom_tags,
+        data: SpriteManagerInstruction::EquipSprite.try_to_vec().unwrap(),
+    }
+}
+
+pub fn unequip_sprite(
+    program_id: &Pubkey,
+    sprite_account: &Pubkey,
+    base_mint: &Pubkey,
+    base_token_account: &Pubkey,
+    sprite_mint: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*sprite_account, false),
+        AccountMeta::new_readonly(*base_mint, false),
+        AccountMeta::new_readonly(*base_token_account, false),
+        AccountMeta::new_readonly(*sprite_mint, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(find_config_address().0, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: SpriteManagerInstruction::UnequipSprite
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_palette_variant(
+    program_id: &Pubkey,
+    sprite_account: &Pubkey,
+    base_mint: &Pubkey,
+    base_token_account: &Pubkey,
+    sprite_mint: &Pubkey,
+    owner: &Pubkey,
+    variant: Option<String>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*sprite_account, false),
+        AccountMeta::new_readonly(*base_mint, false),
+        AccountMeta::new_readonly(*base_token_account, false),
+        AccountMeta::new_readonly(*sprite_mint, false),
+        AccountMeta::new(*owner, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: SpriteManagerInstruction::SetPaletteVariant(SetPaletteVariantArgs { variant })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_active_variant(
+    program_id: &Pubkey,
+    sprite_account: &Pubkey,
+    base_mint: &Pubkey,
+    base_token_account: &Pubkey,
+    sprite_mint: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*sprite_account, false),
+        AccountMeta::new_readonly(*base_mint, false),
+        AccountMeta::new_readonly(*base_token_account, false),
+        AccountMeta::new_readonly(*sprite_mint, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(find_config_address().0, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: SpriteManagerInstruction::SetActiveVariant
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn initialize_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    limits: ConfigLimits,
+    fees: FeeSchedule,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(find_config_address().0, false),
+        AccountMeta::new(*admin, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(find_program_data_address().0, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: SpriteManagerInstruction::InitializeConfig(InitializeConfigArgs { limits, fees })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn update_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    paused: Option<bool>,
+    limits: Option<ConfigLimits>,
+    fees: Option<FeeSchedule>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(find_config_address().0, false),
+        AccountMeta::new_readonly(*admin, true),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: SpriteManagerInstruction::UpdateConfig(UpdateConfigArgs {
+            paused,
+            limits,
+            fees,
         })
         .try_to_vec()
         .unwrap(),
     }
 }
+
+pub fn propose_admin(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    new_admin: Option<Pubkey>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(find_config_address().0, false),
+        AccountMeta::new_readonly(*admin, true),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: SpriteManagerInstruction::ProposeAdmin(ProposeAdminArgs { new_admin })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn accept_admin(program_id: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(find_config_address().0, false),
+        AccountMeta::new_readonly(*new_admin, true),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: SpriteManagerInstruction::AcceptAdmin.try_to_vec().unwrap(),
+    }
+}
+
+pub fn migrate_sprite_account(
+    program_id: &Pubkey,
+    sprite_account: &Pubkey,
+    base_mint: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*sprite_account, false),
+        AccountMeta::new_readonly(*base_mint, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: SpriteManagerInstruction::MigrateSpriteAccount
+            .try_to_vec()
+            .unwrap(),
+    }
+}
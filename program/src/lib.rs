@@ -1,8 +1,17 @@
+pub mod builders;
+#[cfg(not(target_os = "solana"))]
+pub mod decoder;
+#[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 pub mod error;
+pub mod events;
 pub mod instruction;
 pub mod pda;
 pub mod processor;
+#[cfg(not(target_os = "solana"))]
+pub mod query;
+#[cfg(feature = "serde")]
+pub mod serde_pubkey;
 pub mod state;
 
 pub use solana_program;
@@ -1,6 +1,7 @@
-use solana_program::pubkey::Pubkey;
+use mpl_token_metadata::{processor::find_escrow_account, state::EscrowAuthority};
+use solana_program::{bpf_loader_upgradeable, pubkey::Pubkey};
 
-use crate::state::PREFIX;
+use crate::state::{CONFIG_PREFIX, PREFIX};
 
 /// Sprite account PDA seeds
 ///     "sprite",
@@ -8,3 +9,21 @@ use crate::state::PREFIX;
 pub fn find_sprite_address(mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[PREFIX.as_bytes(), mint.as_ref()], &crate::id())
 }
+
+/// Program config PDA seeds
+///     "config",
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_PREFIX.as_bytes()], &crate::id())
+}
+
+/// The upgradeable loader's ProgramData account of this program, which names its upgrade
+/// authority
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[crate::id().as_ref()], &bpf_loader_upgradeable::id())
+}
+
+/// Token-metadata escrow PDA for a base token, with the sprite account as its creator authority
+pub fn find_escrow_address(base_mint: &Pubkey) -> (Pubkey, u8) {
+    let (sprite_address, _) = find_sprite_address(base_mint);
+    find_escrow_account(base_mint, &EscrowAuthority::Creator(sprite_address))
+}
@@ -1,22 +1,34 @@
 use crate::{
     error::SpriteManagerError,
-    instruction::{SpriteManagerInstruction, StoreSpriteArgs},
-    state::{Key, SolanaAccount, Sprite, SpriteAccount, PREFIX},
+    events::SpriteEvent,
+    instruction::{
+        InitializeConfigArgs, ProposeAdminArgs, SetPaletteVariantArgs, SpriteManagerInstruction,
+        StoreSpriteArgs, UpdateConfigArgs,
+    },
+    state::{
+        AttributeValue, ConfigLimits, FeeSchedule, Key, Palette, ProgramConfig, Rendition,
+        SolanaAccount, Sprite, SpriteAccount, SpriteFamily, Timestamp, CONFIG_PREFIX,
+        MAX_ATTRIBUTES, MAX_ATTRIBUTE_KEY_LEN, MAX_ATTRIBUTE_VALUE_LEN, MAX_FEE_EXEMPT_AUTHORITIES,
+        MAX_PALETTE_COLORS, MAX_RENDITIONS, PREFIX,
+    },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
-use mpl_token_metadata::state::{EscrowAuthority, ESCROW_POSTFIX};
+use mpl_token_metadata::state::{EscrowAuthority, Metadata, TokenMetadataAccount, ESCROW_POSTFIX};
 use mpl_utils::{
     assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
     resize_or_reallocate_account_raw,
 };
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
+    program_error::ProgramError,
     program_memory::sol_memcpy,
     program_pack::Pack,
     pubkey::Pubkey,
+    system_instruction,
 };
 
 pub struct Processor;
@@ -35,6 +47,27 @@ impl Processor {
             SpriteManagerInstruction::StoreSprite(args) => {
                 process_store_sprite(program_id, accounts, args)
             }
+            SpriteManagerInstruction::EquipSprite => process_equip_sprite(program_id, accounts),
+            SpriteManagerInstruction::UnequipSprite => process_unequip_sprite(program_id, accounts),
+            SpriteManagerInstruction::SetPaletteVariant(args) => {
+                process_set_palette_variant(program_id, accounts, args)
+            }
+            SpriteManagerInstruction::SetActiveVariant => {
+                process_set_active_variant(program_id, accounts)
+            }
+            SpriteManagerInstruction::InitializeConfig(args) => {
+                process_initialize_config(program_id, accounts, args)
+            }
+            SpriteManagerInstruction::UpdateConfig(args) => {
+                process_update_config(program_id, accounts, args)
+            }
+            SpriteManagerInstruction::ProposeAdmin(args) => {
+                process_propose_admin(program_id, accounts, args)
+            }
+            SpriteManagerInstruction::AcceptAdmin => process_accept_admin(program_id, accounts),
+            SpriteManagerInstruction::MigrateSpriteAccount => {
+                process_migrate_sprite_account(program_id, accounts)
+            }
         }
     }
 }
@@ -55,6 +88,9 @@ pub fn process_create_sprite_account(
     let _tm_program_info = next_account_info(account_info_iter)?;
     let system_program_info = next_account_info(account_info_iter)?;
     let sysvar_ix_account_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+
+    assert_active_config(program_id, config_info)?;
 
     let sprite_pda_bump = assert_derivation(
         program_id,
@@ -79,9 +115,12 @@ pub fn process_create_sprite_account(
         &[sprite_pda_bump],
     ];
 
+    let now = Timestamp::now()?;
     let sprite_account = SpriteAccount {
         key: Key::SpriteAccount,
         base_mint: *mint_info.key,
+        created_at: now,
+        updated_at: now,
         ..SpriteAccount::default()
     };
 
@@ -99,7 +138,7 @@ pub fn process_create_sprite_account(
     )?;
 
     sol_memcpy(
-        &mut **sprite_pda_info
+        &mut sprite_pda_info
             .try_borrow_mut_data()
             .map_err(|_| SpriteManagerError::FailedToBorrowAccountData)?,
         &serialized_data,
@@ -136,6 +175,12 @@ pub fn process_create_sprite_account(
         &[sprite_signer_seeds],
     )?;
 
+    SpriteEvent::SpriteAccountCreated {
+        base_mint: *mint_info.key,
+        sprite_account: *sprite_pda_info.key,
+    }
+    .emit();
+
     Ok(())
 }
 
@@ -149,6 +194,7 @@ pub fn process_store_sprite(
     let escrow_info = next_account_info(account_info_iter)?;
     let base_mint_info = next_account_info(account_info_iter)?;
     let sprite_mint_info = next_account_info(account_info_iter)?;
+    let sprite_metadata_info = next_account_info(account_info_iter)?;
     let sprite_mint_src_info = next_account_info(account_info_iter)?;
     let sprite_mint_dst_info = next_account_info(account_info_iter)?;
     let payer_info = next_account_info(account_info_iter)?;
@@ -156,9 +202,45 @@ pub fn process_store_sprite(
     let system_program_info = next_account_info(account_info_iter)?;
     let token_program_info = next_account_info(account_info_iter)?;
     let _associated_token_account_program_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let treasury_info = next_account_info(account_info_iter)?;
+
+    let config = assert_active_config(program_id, config_info)?;
 
     assert_signer(payer_info)?;
 
+    if let Some(palette) = &args.palette {
+        assert_valid_palette(palette)?;
+    }
+    assert_valid_attributes(&args.attributes)?;
+    assert_valid_renditions(&args.renditions)?;
+    assert_within_limits(&config.limits, &args)?;
+
+    if !config.fees.is_exempt(payer_info.key) {
+        charge_store_fee(
+            &config.fees,
+            payer_info,
+            treasury_info,
+            account_info_iter,
+            system_program_info,
+            token_program_info,
+        )?;
+    }
+
+    // The artist is taken from the sprite's metadata rather than trusting the depositor.
+    let sprite_metadata = Metadata::from_account_info(sprite_metadata_info)?;
+    if sprite_metadata.mint != *sprite_mint_info.key {
+        return Err(SpriteManagerError::MetadataMintMismatch.into());
+    }
+    let artist = sprite_metadata
+        .data
+        .creators
+        .unwrap_or_default()
+        .into_iter()
+        .find(|creator| creator.verified)
+        .ok_or(SpriteManagerError::NoVerifiedCreator)?
+        .address;
+
     let mut escrow_seeds = vec![
         mpl_token_metadata::state::PREFIX.as_bytes(),
         mpl_token_metadata::ID.as_ref(),
@@ -179,12 +261,6 @@ pub fn process_store_sprite(
         SpriteManagerError::DerivedKeyInvalid,
     )?;
 
-    // Deserialize the token accounts and perform checks.
-    let attribute_src = spl_token::state::Account::unpack(&sprite_mint_src_info.data.borrow())?;
-    assert!(attribute_src.mint == *sprite_mint_info.key);
-    assert!(attribute_src.delegate.is_none());
-    assert!(attribute_src.amount >= 1);
-
     let sprite_seeds = &[PREFIX.as_bytes(), base_mint_info.key.as_ref()];
 
     let sprite_bump_seed = assert_derivation(
@@ -200,6 +276,33 @@ pub fn process_store_sprite(
         &[sprite_bump_seed],
     ];
 
+    let now = Timestamp::now()?;
+    let mut sprite_account = SpriteAccount::from_account_info(sprite_pda_info)?;
+    if sprite_account.find_sprite(sprite_mint_info.key).is_some() {
+        return Err(SpriteManagerError::SpriteAlreadyStored.into());
+    }
+    if sprite_account.sprites.len() >= config.limits.max_sprites_per_account as usize {
+        return Err(SpriteManagerError::LimitExceeded.into());
+    }
+
+    // Deserialize the token accounts and perform checks.
+    let attribute_src = spl_token::state::Account::unpack(&sprite_mint_src_info.data.borrow())?;
+    if attribute_src.mint != *sprite_mint_info.key
+        || attribute_src.owner != *payer_info.key
+        || attribute_src.delegate.is_some()
+        || attribute_src.amount < 1
+    {
+        return Err(SpriteManagerError::InvalidTokenAccount.into());
+    }
+    if *sprite_mint_dst_info.key
+        != spl_associated_token_account::get_associated_token_address(
+            escrow_info.key,
+            sprite_mint_info.key,
+        )
+    {
+        return Err(SpriteManagerError::DerivedKeyInvalid.into());
+    }
+
     // Only try to create the ATA if the account doesn't already exist.
     if *sprite_mint_dst_info.owner != spl_token::ID && sprite_mint_dst_info.lamports() == 0 {
         // Allocate the escrow accounts new ATA.
@@ -221,36 +324,60 @@ pub fn process_store_sprite(
                 token_program_info.clone(),
             ],
         )?;
+    }
 
-        // Transfer the token from the current owner into the escrow.
-        let transfer_ix = spl_token::instruction::transfer(
-            &spl_token::id(),
-            sprite_mint_src_info.key,
-            sprite_mint_dst_info.key,
-            payer_info.key,
-            &[payer_info.key],
-            1,
-        )?;
+    // Transfer the token from the current owner into the escrow.
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        sprite_mint_src_info.key,
+        sprite_mint_dst_info.key,
+        payer_info.key,
+        &[payer_info.key],
+        1,
+    )?;
 
-        invoke(
-            &transfer_ix,
-            &[
-                sprite_mint_src_info.clone(),
-                sprite_mint_dst_info.clone(),
-                payer_info.clone(),
-                token_program_info.clone(),
-            ],
-        )?;
-    }
+    invoke(
+        &transfer_ix,
+        &[
+            sprite_mint_src_info.clone(),
+            sprite_mint_dst_info.clone(),
+            payer_info.clone(),
+            token_program_info.clone(),
+        ],
+    )?;
 
-    let mut sprite_account = SpriteAccount::from_account_info(sprite_pda_info)?;
+    sprite_account.updated_at = now;
+    if let Some(family_id) = args.family {
+        match sprite_account.find_family_mut(&family_id) {
+            Some(family) => family.variant_count += 1,
+            // The first variant stored for a family is the one shown.
+            None => sprite_account.families.push(SpriteFamily {
+                id: family_id,
+                active_variant: *sprite_mint_info.key,
+                variant_count: 1,
+            }),
+        }
+    }
     sprite_account.sprites.push(Sprite {
         name: args.name,
         description: args.description,
         perspective_tags: args.perspective_tags,
         style_tags: args.style_tags,
         custom_tags: args.custom_tags,
+        attributes: args.attributes,
         mint: *sprite_mint_info.key,
+        slot: args.slot,
+        z_order: args.z_order,
+        equipped: false,
+        palette: args.palette,
+        palette_variant: None,
+        artist,
+        license: args.license,
+        attribution: args.attribution,
+        family: args.family,
+        renditions: args.renditions,
+        created_at: now,
+        updated_at: now,
     });
 
     let serialized_data = sprite_account
@@ -265,7 +392,607 @@ pub fn process_store_sprite(
     )?;
 
     sol_memcpy(
-        &mut **sprite_pda_info
+        &mut sprite_pda_info
+            .try_borrow_mut_data()
+            .map_err(|_| SpriteManagerError::FailedToBorrowAccountData)?,
+        &serialized_data,
+        serialized_data.len(),
+    );
+
+    SpriteEvent::SpriteStored {
+        base_mint: *base_mint_info.key,
+        sprite_mint: *sprite_mint_info.key,
+        slot: args.slot,
+    }
+    .emit();
+
+    Ok(())
+}
+
+pub fn process_equip_sprite(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let sprite_pda_info = next_account_info(account_info_iter)?;
+    let base_mint_info = next_account_info(account_info_iter)?;
+    let base_token_account_info = next_account_info(account_info_iter)?;
+    let sprite_mint_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+
+    assert_active_config(program_id, config_info)?;
+
+    assert_signer(owner_info)?;
+    assert_derivation(
+        program_id,
+        sprite_pda_info,
+        &[PREFIX.as_bytes(), base_mint_info.key.as_ref()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+    assert_base_token_holder(base_token_account_info, base_mint_info, owner_info)?;
+
+    let mut sprite_account = SpriteAccount::from_account_info(sprite_pda_info)?;
+    let sprite = sprite_account
+        .find_sprite(sprite_mint_info.key)
+        .ok_or(SpriteManagerError::SpriteNotFound)?;
+    let slot = sprite.slot;
+
+    // Only the active variant of a family is worn, SetActiveVariant swaps it for another.
+    if let Some(family_id) = sprite.family {
+        let active_variant = sprite_account
+            .find_family(&family_id)
+            .map(|family| family.active_variant);
+        if active_variant != Some(*sprite_mint_info.key) {
+            return Err(SpriteManagerError::VariantNotActive.into());
+        }
+    }
+
+    // Only one sprite can be worn in each slot at a time.
+    if sprite_account
+        .loadout()
+        .iter()
+        .any(|sprite| sprite.slot == slot)
+    {
+        return Err(SpriteManagerError::SlotOccupied.into());
+    }
+
+    let now = Timestamp::now()?;
+    sprite_account.updated_at = now;
+    let sprite = sprite_account
+        .find_sprite_mut(sprite_mint_info.key)
+        .ok_or(SpriteManagerError::SpriteNotFound)?;
+    sprite.equipped = true;
+    sprite.updated_at = now;
+
+    let serialized_data = sprite_account
+        .try_to_vec()
+        .map_err(|_| SpriteManagerError::FailedToSerialize)?;
+    // Without a payer the account cannot grow to hold an original-layout account in the current
+    // layout.
+    if serialized_data.len() > sprite_pda_info.data_len() {
+        return Err(SpriteManagerError::SpriteAccountNotMigrated.into());
+    }
+
+    sol_memcpy(
+        &mut sprite_pda_info
+            .try_borrow_mut_data()
+            .map_err(|_| SpriteManagerError::FailedToBorrowAccountData)?,
+        &serialized_data,
+        serialized_data.len(),
+    );
+
+    SpriteEvent::SpriteEquipped {
+        base_mint: *base_mint_info.key,
+        sprite_mint: *sprite_mint_info.key,
+    }
+    .emit();
+
+    Ok(())
+}
+
+pub fn process_unequip_sprite(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let sprite_pda_info = next_account_info(account_info_iter)?;
+    let base_mint_info = next_account_info(account_info_iter)?;
+    let base_token_account_info = next_account_info(account_info_iter)?;
+    let sprite_mint_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+
+    assert_active_config(program_id, config_info)?;
+
+    assert_signer(owner_info)?;
+    assert_derivation(
+        program_id,
+        sprite_pda_info,
+        &[PREFIX.as_bytes(), base_mint_info.key.as_ref()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+    assert_base_token_holder(base_token_account_info, base_mint_info, owner_info)?;
+
+    let now = Timestamp::now()?;
+    let mut sprite_account = SpriteAccount::from_account_info(sprite_pda_info)?;
+    let sprite = sprite_account
+        .find_sprite_mut(sprite_mint_info.key)
+        .ok_or(SpriteManagerError::SpriteNotFound)?;
+
+    if !sprite.equipped {
+        return Err(SpriteManagerError::SpriteNotEquipped.into());
+    }
+    sprite.equipped = false;
+    sprite.updated_at = now;
+    sprite_account.updated_at = now;
+
+    let serialized_data = sprite_account
+        .try_to_vec()
+        .map_err(|_| SpriteManagerError::FailedToSerialize)?;
+    // Without a payer the account cannot grow to hold an original-layout account in the current
+    // layout.
+    if serialized_data.len() > sprite_pda_info.data_len() {
+        return Err(SpriteManagerError::SpriteAccountNotMigrated.into());
+    }
+
+    sol_memcpy(
+        &mut sprite_pda_info
+            .try_borrow_mut_data()
+            .map_err(|_| SpriteManagerError::FailedToBorrowAccountData)?,
+        &serialized_data,
+        serialized_data.len(),
+    );
+
+    SpriteEvent::SpriteUnequipped {
+        base_mint: *base_mint_info.key,
+        sprite_mint: *sprite_mint_info.key,
+    }
+    .emit();
+
+    Ok(())
+}
+
+pub fn process_set_palette_variant(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetPaletteVariantArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let sprite_pda_info = next_account_info(account_info_iter)?;
+    let base_mint_info = next_account_info(account_info_iter)?;
+    let base_token_account_info = next_account_info(account_info_iter)?;
+    let sprite_mint_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+
+    assert_active_config(program_id, config_info)?;
+
+    assert_signer(owner_info)?;
+    assert_derivation(
+        program_id,
+        sprite_pda_info,
+        &[PREFIX.as_bytes(), base_mint_info.key.as_ref()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+    assert_base_token_holder(base_token_account_info, base_mint_info, owner_info)?;
+
+    let now = Timestamp::now()?;
+    let mut sprite_account = SpriteAccount::from_account_info(sprite_pda_info)?;
+    let sprite = sprite_account
+        .find_sprite_mut(sprite_mint_info.key)
+        .ok_or(SpriteManagerError::SpriteNotFound)?;
+
+    if let Some(variant) = &args.variant {
+        sprite
+            .palette
+            .as_ref()
+            .and_then(|palette| palette.find_variant(variant))
+            .ok_or(SpriteManagerError::PaletteVariantNotFound)?;
+    }
+    sprite.palette_variant = args.variant;
+    sprite.updated_at = now;
+    sprite_account.updated_at = now;
+
+    let serialized_data = sprite_account
+        .try_to_vec()
+        .map_err(|_| SpriteManagerError::FailedToSerialize)?;
+
+    resize_or_reallocate_account_raw(
+        sprite_pda_info,
+        owner_info,
+        system_program_info,
+        serialized_data.len(),
+    )?;
+
+    sol_memcpy(
+        &mut sprite_pda_info
+            .try_borrow_mut_data()
+            .map_err(|_| SpriteManagerError::FailedToBorrowAccountData)?,
+        &serialized_data,
+        serialized_data.len(),
+    );
+
+    SpriteEvent::SpriteUpdated {
+        base_mint: *base_mint_info.key,
+        sprite_mint: *sprite_mint_info.key,
+    }
+    .emit();
+
+    Ok(())
+}
+
+pub fn process_set_active_variant(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let sprite_pda_info = next_account_info(account_info_iter)?;
+    let base_mint_info = next_account_info(account_info_iter)?;
+    let base_token_account_info = next_account_info(account_info_iter)?;
+    let sprite_mint_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+
+    assert_active_config(program_id, config_info)?;
+
+    assert_signer(owner_info)?;
+    assert_derivation(
+        program_id,
+        sprite_pda_info,
+        &[PREFIX.as_bytes(), base_mint_info.key.as_ref()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+    assert_base_token_holder(base_token_account_info, base_mint_info, owner_info)?;
+
+    let mut sprite_account = SpriteAccount::from_account_info(sprite_pda_info)?;
+    let sprite = sprite_account
+        .find_sprite(sprite_mint_info.key)
+        .ok_or(SpriteManagerError::SpriteNotFound)?;
+    let family_id = sprite.family.ok_or(SpriteManagerError::SpriteHasNoFamily)?;
+    let slot = sprite.slot;
+
+    let family = sprite_account
+        .find_family(&family_id)
+        .ok_or(SpriteManagerError::SpriteHasNoFamily)?;
+    let previous_mint = family.active_variant;
+    if previous_mint == *sprite_mint_info.key {
+        return Ok(());
+    }
+
+    // An equipped family is re-dressed with the new variant in place of the old one.
+    let previous = sprite_account.find_sprite(&previous_mint);
+    let was_equipped = matches!(previous, Some(previous) if previous.equipped);
+    if was_equipped
+        && previous.map(|previous| previous.slot) != Some(slot)
+        && sprite_account
+            .loadout()
+            .iter()
+            .any(|sprite| sprite.slot == slot)
+    {
+        return Err(SpriteManagerError::SlotOccupied.into());
+    }
+
+    let now = Timestamp::now()?;
+    sprite_account.updated_at = now;
+    if let Some(family) = sprite_account.find_family_mut(&family_id) {
+        family.active_variant = *sprite_mint_info.key;
+    }
+    if was_equipped {
+        if let Some(previous) = sprite_account.find_sprite_mut(&previous_mint) {
+            previous.equipped = false;
+            previous.updated_at = now;
+        }
+    }
+    if let Some(sprite) = sprite_account.find_sprite_mut(sprite_mint_info.key) {
+        sprite.equipped |= was_equipped;
+        sprite.updated_at = now;
+    }
+
+    let serialized_data = sprite_account
+        .try_to_vec()
+        .map_err(|_| SpriteManagerError::FailedToSerialize)?;
+    // Without a payer the account cannot grow to hold an original-layout account in the current
+    // layout.
+    if serialized_data.len() > sprite_pda_info.data_len() {
+        return Err(SpriteManagerError::SpriteAccountNotMigrated.into());
+    }
+
+    sol_memcpy(
+        &mut sprite_pda_info
+            .try_borrow_mut_data()
+            .map_err(|_| SpriteManagerError::FailedToBorrowAccountData)?,
+        &serialized_data,
+        serialized_data.len(),
+    );
+
+    SpriteEvent::ActiveVariantChanged {
+        base_mint: *base_mint_info.key,
+        family: family_id,
+        previous_variant: previous_mint,
+        active_variant: *sprite_mint_info.key,
+    }
+    .emit();
+
+    Ok(())
+}
+
+pub fn process_initialize_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: InitializeConfigArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let program_data_info = next_account_info(account_info_iter)?;
+
+    assert_signer(admin_info)?;
+    assert_upgrade_authority(program_id, program_data_info, admin_info)?;
+    let config_bump = assert_derivation(
+        program_id,
+        config_info,
+        &[CONFIG_PREFIX.as_bytes()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+
+    if !config_info.data_is_empty() {
+        return Err(SpriteManagerError::ConfigAlreadyInitialized.into());
+    }
+
+    create_or_allocate_account_raw(
+        *program_id,
+        config_info,
+        system_program_info,
+        admin_info,
+        ProgramConfig::size(),
+        &[CONFIG_PREFIX.as_bytes(), &[config_bump]],
+    )?;
+
+    let config = ProgramConfig {
+        key: Key::ProgramConfig,
+        admin: *admin_info.key,
+        pending_admin: None,
+        paused: false,
+        limits: args.limits,
+        fees: args.fees,
+    };
+    assert_valid_fee_schedule(&config.fees)?;
+
+    write_config(config_info, &config)?;
+    SpriteEvent::ConfigInitialized {
+        admin: config.admin,
+    }
+    .emit();
+
+    Ok(())
+}
+
+pub fn process_update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: UpdateConfigArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+
+    let mut config = load_config_as_admin(program_id, config_info, admin_info)?;
+    if let Some(paused) = args.paused {
+        config.paused = paused;
+    }
+    if let Some(limits) = args.limits {
+        config.limits = limits;
+    }
+    if let Some(fees) = args.fees {
+        assert_valid_fee_schedule(&fees)?;
+        config.fees = fees;
+    }
+
+    write_config(config_info, &config)?;
+    SpriteEvent::ConfigUpdated {
+        admin: config.admin,
+    }
+    .emit();
+
+    Ok(())
+}
+
+pub fn process_propose_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ProposeAdminArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+
+    let mut config = load_config_as_admin(program_id, config_info, admin_info)?;
+    config.pending_admin = args.new_admin;
+
+    write_config(config_info, &config)?;
+    SpriteEvent::AdminProposed {
+        admin: config.admin,
+        pending_admin: config.pending_admin,
+    }
+    .emit();
+
+    Ok(())
+}
+
+pub fn process_accept_admin(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let new_admin_info = next_account_info(account_info_iter)?;
+
+    assert_signer(new_admin_info)?;
+    assert_derivation(
+        program_id,
+        config_info,
+        &[CONFIG_PREFIX.as_bytes()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+
+    let mut config = ProgramConfig::from_account_info(config_info)?;
+    if config.pending_admin != Some(*new_admin_info.key) {
+        return Err(SpriteManagerError::NotPendingAdmin.into());
+    }
+    let previous_admin = config.admin;
+    config.admin = *new_admin_info.key;
+    config.pending_admin = None;
+
+    write_config(config_info, &config)?;
+    SpriteEvent::AdminChanged {
+        previous_admin,
+        admin: config.admin,
+    }
+    .emit();
+
+    Ok(())
+}
+
+/// Loads the program config and fails if the program is paused. An uninitialized config PDA
+/// yields the default config.
+pub fn process_migrate_sprite_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let sprite_pda_info = next_account_info(account_info_iter)?;
+    let base_mint_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_derivation(
+        program_id,
+        sprite_pda_info,
+        &[PREFIX.as_bytes(), base_mint_info.key.as_ref()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+
+    let sprite_account = SpriteAccount::from_account_info(sprite_pda_info)?;
+    if sprite_pda_info.data.borrow().first() != Some(&(Key::SpriteAccountV1 as u8)) {
+        return Err(SpriteManagerError::SpriteAccountAlreadyMigrated.into());
+    }
+
+    let serialized_data = sprite_account
+        .try_to_vec()
+        .map_err(|_| SpriteManagerError::FailedToSerialize)?;
+
+    resize_or_reallocate_account_raw(
+        sprite_pda_info,
+        payer_info,
+        system_program_info,
+        serialized_data.len(),
+    )?;
+
+    sol_memcpy(
+        &mut sprite_pda_info
+            .try_borrow_mut_data()
+            .map_err(|_| SpriteManagerError::FailedToBorrowAccountData)?,
+        &serialized_data,
+        serialized_data.len(),
+    );
+
+    Ok(())
+}
+
+fn assert_active_config(
+    program_id: &Pubkey,
+    config_info: &AccountInfo,
+) -> Result<ProgramConfig, ProgramError> {
+    assert_derivation(
+        program_id,
+        config_info,
+        &[CONFIG_PREFIX.as_bytes()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+
+    let config = if config_info.data_is_empty() {
+        ProgramConfig::default()
+    } else {
+        ProgramConfig::from_account_info(config_info)?
+    };
+
+    if config.paused {
+        return Err(SpriteManagerError::ProgramPaused.into());
+    }
+
+    Ok(config)
+}
+
+/// Checks that `authority_info` is the upgrade authority named by the program's ProgramData
+/// account, so only whoever deployed the program can claim its config.
+fn assert_upgrade_authority(
+    program_id: &Pubkey,
+    program_data_info: &AccountInfo,
+    authority_info: &AccountInfo,
+) -> ProgramResult {
+    assert_derivation(
+        &bpf_loader_upgradeable::id(),
+        program_data_info,
+        &[program_id.as_ref()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+    assert_owned_by(
+        program_data_info,
+        &bpf_loader_upgradeable::id(),
+        SpriteManagerError::IncorrectOwner,
+    )?;
+
+    // ProgramData is bincode-encoded: the u32 variant index 3, the u64 deployment slot and an
+    // optional upgrade authority, which is absent once the program is made immutable.
+    let data = program_data_info
+        .try_borrow_data()
+        .map_err(|_| SpriteManagerError::FailedToBorrowAccountData)?;
+    let metadata = data
+        .get(..UpgradeableLoaderState::size_of_programdata_metadata())
+        .ok_or(SpriteManagerError::NotUpgradeAuthority)?;
+    let (variant, rest) = metadata.split_at(4);
+    let (_slot, authority) = rest.split_at(8);
+    if variant != 3u32.to_le_bytes()
+        || authority[0] != 1
+        || authority[1..] != authority_info.key.to_bytes()
+    {
+        return Err(SpriteManagerError::NotUpgradeAuthority.into());
+    }
+
+    Ok(())
+}
+
+/// Loads the initialized program config and checks that `admin_info` is its signing admin.
+fn load_config_as_admin(
+    program_id: &Pubkey,
+    config_info: &AccountInfo,
+    admin_info: &AccountInfo,
+) -> Result<ProgramConfig, ProgramError> {
+    assert_signer(admin_info)?;
+    assert_derivation(
+        program_id,
+        config_info,
+        &[CONFIG_PREFIX.as_bytes()],
+        SpriteManagerError::DerivedKeyInvalid,
+    )?;
+
+    let config = ProgramConfig::from_account_info(config_info)?;
+    if config.admin != *admin_info.key {
+        return Err(SpriteManagerError::InvalidAdmin.into());
+    }
+
+    Ok(config)
+}
+
+fn write_config(config_info: &AccountInfo, config: &ProgramConfig) -> ProgramResult {
+    let mut serialized_data = config
+        .try_to_vec()
+        .map_err(|_| SpriteManagerError::FailedToSerialize)?;
+    ProgramConfig::pad_length(&mut serialized_data)?;
+
+    sol_memcpy(
+        &mut config_info
             .try_borrow_mut_data()
             .map_err(|_| SpriteManagerError::FailedToBorrowAccountData)?,
         &serialized_data,
@@ -274,3 +1001,194 @@ pub fn process_store_sprite(
 
     Ok(())
 }
+
+/// Charges the configured store fee from `payer_info` to the treasury, taking the fee token
+/// accounts from `account_info_iter` when a token fee is set.
+fn charge_store_fee<'a, 'b: 'a>(
+    fees: &FeeSchedule,
+    payer_info: &'a AccountInfo<'b>,
+    treasury_info: &'a AccountInfo<'b>,
+    account_info_iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+    system_program_info: &'a AccountInfo<'b>,
+    token_program_info: &'a AccountInfo<'b>,
+) -> ProgramResult {
+    if fees.store_fee_lamports == 0 && fees.token_fee.is_none() {
+        return Ok(());
+    }
+
+    if *treasury_info.key != fees.treasury {
+        return Err(SpriteManagerError::InvalidTreasury.into());
+    }
+
+    if fees.store_fee_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(
+                payer_info.key,
+                treasury_info.key,
+                fees.store_fee_lamports,
+            ),
+            &[
+                payer_info.clone(),
+                treasury_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+        msg!(
+            "Store fee: {} lamports from {} to {}",
+            fees.store_fee_lamports,
+            payer_info.key,
+            treasury_info.key
+        );
+    }
+
+    if let Some(token_fee) = &fees.token_fee {
+        let fee_token_src_info = next_account_info(account_info_iter)?;
+        let fee_token_dst_info = next_account_info(account_info_iter)?;
+
+        assert_owned_by(
+            fee_token_dst_info,
+            &spl_token::ID,
+            SpriteManagerError::InvalidTreasury,
+        )?;
+        let fee_token_dst = spl_token::state::Account::unpack(&fee_token_dst_info.data.borrow())?;
+        if fee_token_dst.owner != fees.treasury || fee_token_dst.mint != token_fee.mint {
+            return Err(SpriteManagerError::InvalidTreasury.into());
+        }
+
+        invoke(
+            &spl_token::instruction::transfer(
+                &spl_token::id(),
+                fee_token_src_info.key,
+                fee_token_dst_info.key,
+                payer_info.key,
+                &[payer_info.key],
+                token_fee.amount,
+            )?,
+            &[
+                fee_token_src_info.clone(),
+                fee_token_dst_info.clone(),
+                payer_info.clone(),
+                token_program_info.clone(),
+            ],
+        )?;
+        msg!(
+            "Store fee: {} of token {} from {} to {}",
+            token_fee.amount,
+            token_fee.mint,
+            payer_info.key,
+            fees.treasury
+        );
+    }
+
+    SpriteEvent::FeeCharged {
+        payer: *payer_info.key,
+        treasury: fees.treasury,
+        lamports: fees.store_fee_lamports,
+        token_fee: fees.token_fee,
+    }
+    .emit();
+
+    Ok(())
+}
+
+fn assert_valid_fee_schedule(fees: &FeeSchedule) -> ProgramResult {
+    if fees.exempt_authorities.len() > MAX_FEE_EXEMPT_AUTHORITIES {
+        return Err(SpriteManagerError::LimitExceeded.into());
+    }
+
+    Ok(())
+}
+
+/// Checks the sprite's text fields against the configured size limits.
+fn assert_within_limits(limits: &ConfigLimits, args: &StoreSpriteArgs) -> ProgramResult {
+    if args.name.len() > limits.max_name_len as usize
+        || args.description.len() > limits.max_description_len as usize
+        || args.custom_tags.len() > limits.max_custom_tags as usize
+        || args
+            .custom_tags
+            .iter()
+            .any(|tag| tag.len() > limits.max_custom_tag_len as usize)
+    {
+        return Err(SpriteManagerError::LimitExceeded.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that every palette variant recolors the full base palette and has a unique name.
+fn assert_valid_palette(palette: &Palette) -> ProgramResult {
+    if palette.colors.is_empty() || palette.colors.len() > MAX_PALETTE_COLORS {
+        return Err(SpriteManagerError::InvalidPalette.into());
+    }
+
+    for (i, variant) in palette.variants.iter().enumerate() {
+        if variant.colors.len() != palette.colors.len()
+            || palette.variants[..i]
+                .iter()
+                .any(|other| other.name == variant.name)
+        {
+            return Err(SpriteManagerError::InvalidPalette.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that attribute keys are unique and that keys, values and their count stay within limits.
+fn assert_valid_attributes(attributes: &[(String, AttributeValue)]) -> ProgramResult {
+    if attributes.len() > MAX_ATTRIBUTES {
+        return Err(SpriteManagerError::InvalidAttributes.into());
+    }
+
+    for (i, (key, value)) in attributes.iter().enumerate() {
+        if key.is_empty() || key.len() > MAX_ATTRIBUTE_KEY_LEN {
+            return Err(SpriteManagerError::InvalidAttributes.into());
+        }
+        if let AttributeValue::String(value) = value {
+            if value.len() > MAX_ATTRIBUTE_VALUE_LEN {
+                return Err(SpriteManagerError::InvalidAttributes.into());
+            }
+        }
+        if attributes[..i].iter().any(|(other, _)| other == key) {
+            return Err(SpriteManagerError::DuplicateAttributeKey.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that renditions have usable dimensions and stay within the rendition limit.
+fn assert_valid_renditions(renditions: &[Rendition]) -> ProgramResult {
+    if renditions.len() > MAX_RENDITIONS
+        || renditions
+            .iter()
+            .any(|rendition| rendition.scale == 0 || rendition.width == 0 || rendition.height == 0)
+    {
+        return Err(SpriteManagerError::InvalidRendition.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `owner_info` holds the base token in `token_account_info`.
+fn assert_base_token_holder(
+    token_account_info: &AccountInfo,
+    base_mint_info: &AccountInfo,
+    owner_info: &AccountInfo,
+) -> ProgramResult {
+    assert_owned_by(
+        token_account_info,
+        &spl_token::ID,
+        SpriteManagerError::IncorrectOwner,
+    )?;
+
+    let token_account = spl_token::state::Account::unpack(&token_account_info.data.borrow())?;
+    if token_account.mint != *base_mint_info.key
+        || token_account.owner != *owner_info.key
+        || token_account.amount < 1
+    {
+        return Err(SpriteManagerError::NotBaseTokenHolder.into());
+    }
+
+    Ok(())
+}
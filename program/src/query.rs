@@ -0,0 +1,122 @@
+//! Filters over the sprites of a [`SpriteAccount`].
+//!
+//! A [`SpriteQuery`] matches sprites by tag or by text and is combined with `&`, `|` and `!`:
+//!
+//! ```
+//! use sprite_manager::{
+//!     query::SpriteQuery,
+//!     state::{PerspectiveTags, StyleTags},
+//! };
+//!
+//! let query = SpriteQuery::Style(StyleTags::Pixel)
+//!     & (SpriteQuery::Perspective(PerspectiveTags::TopDown) | SpriteQuery::text("hat"))
+//!     & !SpriteQuery::custom_tag("retired");
+//! ```
+
+use std::ops::{BitAnd, BitOr, Not};
+
+use crate::state::{PerspectiveTags, Sprite, SpriteAccount, StyleTags};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpriteQuery {
+    /// The sprite has the perspective tag.
+    Perspective(PerspectiveTags),
+    /// The sprite has the style tag.
+    Style(StyleTags),
+    /// The sprite has the custom tag, compared exactly.
+    CustomTag(String),
+    /// The sprite's name or description contains the text, ignoring case.
+    Text(String),
+    /// Every query matches. Matches every sprite when empty.
+    And(Vec<SpriteQuery>),
+    /// At least one query matches. Matches no sprite when empty.
+    Or(Vec<SpriteQuery>),
+    Not(Box<SpriteQuery>),
+}
+
+impl SpriteQuery {
+    pub fn custom_tag(tag: impl Into<String>) -> Self {
+        SpriteQuery::CustomTag(tag.into())
+    }
+
+    pub fn text(text: impl Into<String>) -> Self {
+        SpriteQuery::Text(text.into())
+    }
+
+    /// Matches sprites that match every one of `queries`.
+    pub fn all(queries: impl IntoIterator<Item = SpriteQuery>) -> Self {
+        SpriteQuery::And(queries.into_iter().collect())
+    }
+
+    /// Matches sprites that match any of `queries`.
+    pub fn any(queries: impl IntoIterator<Item = SpriteQuery>) -> Self {
+        SpriteQuery::Or(queries.into_iter().collect())
+    }
+
+    pub fn matches(&self, sprite: &Sprite) -> bool {
+        match self {
+            SpriteQuery::Perspective(tag) => sprite.perspective_tags.contains(tag),
+            SpriteQuery::Style(tag) => sprite.style_tags.contains(tag),
+            SpriteQuery::CustomTag(tag) => sprite.custom_tags.contains(tag),
+            SpriteQuery::Text(text) => text_matches(&sprite.name, &sprite.description, text),
+            SpriteQuery::And(queries) => queries.iter().all(|query| query.matches(sprite)),
+            SpriteQuery::Or(queries) => queries.iter().any(|query| query.matches(sprite)),
+            SpriteQuery::Not(query) => !query.matches(sprite),
+        }
+    }
+}
+
+/// Whether `name` or `description` contains `text`, ignoring case. This is the matching used by
+/// [`SpriteQuery::Text`], exposed so that other stores of sprites can match the same way.
+pub fn text_matches(name: &str, description: &str, text: &str) -> bool {
+    let text = text.to_lowercase();
+    name.to_lowercase().contains(&text) || description.to_lowercase().contains(&text)
+}
+
+impl BitAnd for SpriteQuery {
+    type Output = SpriteQuery;
+
+    fn bitand(self, rhs: SpriteQuery) -> SpriteQuery {
+        match self {
+            SpriteQuery::And(mut queries) => {
+                queries.push(rhs);
+                SpriteQuery::And(queries)
+            }
+            lhs => SpriteQuery::And(vec![lhs, rhs]),
+        }
+    }
+}
+
+impl BitOr for SpriteQuery {
+    type Output = SpriteQuery;
+
+    fn bitor(self, rhs: SpriteQuery) -> SpriteQuery {
+        match self {
+            SpriteQuery::Or(mut queries) => {
+                queries.push(rhs);
+                SpriteQuery::Or(queries)
+            }
+            lhs => SpriteQuery::Or(vec![lhs, rhs]),
+        }
+    }
+}
+
+impl Not for SpriteQuery {
+    type Output = SpriteQuery;
+
+    fn not(self) -> SpriteQuery {
+        match self {
+            SpriteQuery::Not(query) => *query,
+            query => SpriteQuery::Not(Box::new(query)),
+        }
+    }
+}
+
+impl SpriteAccount {
+    /// The stored sprites that match `query`, in storage order.
+    pub fn query<'a>(&'a self, query: &'a SpriteQuery) -> impl Iterator<Item = &'a Sprite> + 'a {
+        self.sprites
+            .iter()
+            .filter(move |sprite| query.matches(sprite))
+    }
+}
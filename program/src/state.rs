@@ -1,29 +1,54 @@
+use std::{cmp::Reverse, fmt};
+
 use borsh::{maybestd::io::Error as BorshError, BorshDeserialize, BorshSerialize};
 use mpl_utils::assert_owned_by;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use shank::ShankAccount;
-use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
-use std::io::ErrorKind;
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, program_error::ProgramError, pubkey::Pubkey,
+    sysvar::Sysvar,
+};
 
 use crate::error::SpriteManagerError;
 
 pub const PREFIX: &str = "sprite";
+pub const CONFIG_PREFIX: &str = "config";
+
+/// Indexed palettes are limited to what fits in a byte-sized color index.
+pub const MAX_PALETTE_COLORS: usize = 256;
+
+pub const MAX_ATTRIBUTES: usize = 16;
+pub const MAX_ATTRIBUTE_KEY_LEN: usize = 32;
+pub const MAX_ATTRIBUTE_VALUE_LEN: usize = 64;
+
+pub const MAX_RENDITIONS: usize = 8;
+
+pub const MAX_FEE_EXEMPT_AUTHORITIES: usize = 8;
 
 #[repr(C)]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy, FromPrimitive)]
+#[derive(
+    BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy, FromPrimitive, Default,
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub enum Key {
+    #[default]
     Uninitialized,
+    /// A sprite account still in the original `SpriteAccountV1` layout. It is read as a
+    /// `SpriteAccount` and rewritten in the current layout by `MigrateSpriteAccount`.
+    SpriteAccountV1,
+    ProgramConfig,
     SpriteAccount,
 }
 
-impl Default for Key {
-    fn default() -> Self {
-        Key::Uninitialized
-    }
-}
-
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub enum PerspectiveTags {
     RPG,
     TopDown,
@@ -32,6 +57,8 @@ pub enum PerspectiveTags {
 }
 
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub enum StyleTags {
     Pixel,
     Vector,
@@ -39,24 +66,334 @@ pub enum StyleTags {
     Cartoon,
 }
 
+/// The body slot a sprite occupies when it is worn by a character.
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub enum EquipmentSlot {
+    #[default]
+    Body,
+    Hair,
+    Hat,
+    Top,
+    Bottom,
+    Shoes,
+    Weapon,
+    Offhand,
+    Accessory,
+}
+
+/// A point in time as seen by the cluster, taken from the `Clock` sysvar.
+#[derive(
+    Clone, Copy, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Default,
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct Timestamp {
+    pub unix_timestamp: i64,
+    pub slot: u64,
+}
+
+impl Timestamp {
+    pub fn now() -> Result<Self, ProgramError> {
+        let clock = Clock::get()?;
+        Ok(Self {
+            unix_timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+        })
+    }
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum AttributeValue {
+    String(String),
+    Integer(i64),
+    Bool(bool),
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub enum RenditionFormat {
+    #[default]
+    Png,
+    WebP,
+    Ktx2,
+}
+
+/// One stored copy of the sprite art at a given scale and format.
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct Rendition {
+    pub uri: String,
+    /// Integer scale factor relative to the native pixel art, e.g. 1 for 1x and 4 for 4x.
+    pub scale: u8,
+    pub width: u32,
+    pub height: u32,
+    pub format: RenditionFormat,
+    /// SHA-256 of the asset found at `uri`.
+    pub content_hash: [u8; 32],
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub enum License {
+    Cc0,
+    CcBy,
+    CcByNc,
+    #[default]
+    Proprietary,
+    /// Terms published at the given URI.
+    CustomUri(String),
+}
+
+impl fmt::Display for License {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            License::Cc0 => f.write_str("CC0"),
+            License::CcBy => f.write_str("CC-BY"),
+            License::CcByNc => f.write_str("CC-BY-NC"),
+            License::Proprietary => f.write_str("Proprietary"),
+            License::CustomUri(uri) => f.write_str(uri),
+        }
+    }
+}
+
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+/// A named recolor of the base palette. Colors map index for index onto the base palette.
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct PaletteVariant {
+    pub name: String,
+    pub colors: Vec<Rgba>,
+}
+
+/// The indexed palette a pixel sprite is drawn with, plus its palette-swap variants.
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct Palette {
+    pub colors: Vec<Rgba>,
+    pub variants: Vec<PaletteVariant>,
+}
+
+impl Palette {
+    pub fn find_variant(&self, name: &str) -> Option<&PaletteVariant> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+}
+
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct Sprite {
     pub name: String,
     pub description: String,
     pub perspective_tags: Vec<PerspectiveTags>,
     pub style_tags: Vec<StyleTags>,
     pub custom_tags: Vec<String>,
+    pub attributes: Vec<(String, AttributeValue)>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub mint: Pubkey,
+    pub slot: EquipmentSlot,
+    /// Draw order within a loadout, lower values are drawn first.
+    pub z_order: u8,
+    pub equipped: bool,
+    pub palette: Option<Palette>,
+    /// The palette variant selected for this base token, `None` draws the base palette.
+    pub palette_variant: Option<String>,
+    /// The first verified creator of the sprite mint.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
+    pub artist: Pubkey,
+    pub license: License,
+    pub attribution: Option<String>,
+    /// The family this sprite is a variant of, if any.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey::option"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
+    pub family: Option<Pubkey>,
+    pub renditions: Vec<Rendition>,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+impl Sprite {
+    /// Picks the rendition to load for a display at `target_scale`.
+    ///
+    /// Only renditions in `supported_formats` are considered, with earlier formats preferred. The
+    /// smallest scale at or above the target wins so nothing is upscaled; failing that the largest
+    /// available scale is used.
+    pub fn best_rendition(
+        &self,
+        target_scale: u8,
+        supported_formats: &[RenditionFormat],
+    ) -> Option<&Rendition> {
+        let format_rank = |rendition: &Rendition| {
+            supported_formats
+                .iter()
+                .position(|format| *format == rendition.format)
+        };
+        let candidates = self
+            .renditions
+            .iter()
+            .filter_map(|rendition| format_rank(rendition).map(|rank| (rendition, rank)));
+
+        candidates
+            .min_by_key(|(rendition, rank)| {
+                if rendition.scale >= target_scale {
+                    (0, rendition.scale, *rank)
+                } else {
+                    (1, u8::MAX - rendition.scale, *rank)
+                }
+            })
+            .map(|(rendition, _)| rendition)
+    }
+
+    pub fn attribute(&self, key: &str) -> Option<&AttributeValue> {
+        self.attributes
+            .iter()
+            .find(|(attribute_key, _)| attribute_key == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the colors the sprite should currently be drawn with.
+    pub fn active_palette(&self) -> Option<&[Rgba]> {
+        let palette = self.palette.as_ref()?;
+        match &self.palette_variant {
+            Some(name) => palette
+                .find_variant(name)
+                .map(|variant| variant.colors.as_slice()),
+            None => Some(palette.colors.as_slice()),
+        }
+    }
+}
+
+/// A group of stored sprites that are variants of one design, only one of which is shown at a time.
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct SpriteFamily {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
+    pub id: Pubkey,
+    /// The mint of the variant currently shown for this family.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
+    pub active_variant: Pubkey,
+    pub variant_count: u32,
 }
 
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub struct SpriteAccount {
     pub key: Key,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub base_mint: Pubkey,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+    pub families: Vec<SpriteFamily>,
     pub sprites: Vec<Sprite>,
 }
 
+impl SpriteAccount {
+    pub fn find_sprite(&self, mint: &Pubkey) -> Option<&Sprite> {
+        self.sprites.iter().find(|sprite| sprite.mint == *mint)
+    }
+
+    pub fn find_sprite_mut(&mut self, mint: &Pubkey) -> Option<&mut Sprite> {
+        self.sprites.iter_mut().find(|sprite| sprite.mint == *mint)
+    }
+
+    pub fn find_family(&self, id: &Pubkey) -> Option<&SpriteFamily> {
+        self.families.iter().find(|family| family.id == *id)
+    }
+
+    pub fn find_family_mut(&mut self, id: &Pubkey) -> Option<&mut SpriteFamily> {
+        self.families.iter_mut().find(|family| family.id == *id)
+    }
+
+    /// Returns the stored sprites that belong to `family`.
+    pub fn variants(&self, family: &Pubkey) -> Vec<&Sprite> {
+        self.sprites
+            .iter()
+            .filter(|sprite| sprite.family.as_ref() == Some(family))
+            .collect()
+    }
+
+    /// Returns the equipped sprites sorted by `z_order`, in the order they should be drawn.
+    pub fn loadout(&self) -> Vec<&Sprite> {
+        let mut loadout: Vec<&Sprite> = self.sprites.iter().filter(|s| s.equipped).collect();
+        loadout.sort_by_key(|sprite| sprite.z_order);
+        loadout
+    }
+
+    /// Returns the sprites whose attribute `key` equals `value`, e.g. `sprites_where("rarity", "epic")`.
+    pub fn sprites_where(&self, key: &str, value: impl Into<AttributeValue>) -> Vec<&Sprite> {
+        let value = value.into();
+        self.sprites
+            .iter()
+            .filter(|sprite| sprite.attribute(key) == Some(&value))
+            .collect()
+    }
+
+    /// Returns all sprites, most recently added first.
+    pub fn sprites_by_created_at(&self) -> Vec<&Sprite> {
+        let mut sprites: Vec<&Sprite> = self.sprites.iter().collect();
+        sprites.sort_by_key(|sprite| Reverse(sprite.created_at));
+        sprites
+    }
+
+    /// Returns all sprites, most recently changed first.
+    pub fn sprites_by_updated_at(&self) -> Vec<&Sprite> {
+        let mut sprites: Vec<&Sprite> = self.sprites.iter().collect();
+        sprites.sort_by_key(|sprite| Reverse(sprite.updated_at));
+        sprites
+    }
+}
+
 impl SolanaAccount for SpriteAccount {
     fn key() -> Key {
         Key::SpriteAccount
@@ -65,6 +402,161 @@ impl SolanaAccount for SpriteAccount {
     fn size() -> usize {
         0
     }
+
+    /// Accounts still in the original layout are converted to the current one.
+    fn safe_deserialize(mut data: &[u8]) -> Result<Self, BorshError> {
+        match data.first().and_then(|key| Key::from_u8(*key)) {
+            Some(Key::SpriteAccountV1) => Ok(SpriteAccountV1::deserialize(&mut data)?.into()),
+            Some(Key::SpriteAccount | Key::Uninitialized) => {
+                BorshDeserialize::deserialize(&mut data)
+            }
+            _ => Err(BorshError::other("DataTypeMismatch")),
+        }
+    }
+}
+
+impl SpriteAccount {
+    /// Whether `data` starts with the key of a sprite account in either layout.
+    pub fn has_sprite_account_key(data: &[u8]) -> bool {
+        matches!(
+            data.first().and_then(|key| Key::from_u8(*key)),
+            Some(Key::SpriteAccount | Key::SpriteAccountV1)
+        )
+    }
+}
+
+/// A sprite as stored by the original layout, before equipment, palettes, attributes, families,
+/// licensing and timestamps were added.
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct SpriteV1 {
+    pub name: String,
+    pub description: String,
+    pub perspective_tags: Vec<PerspectiveTags>,
+    pub style_tags: Vec<StyleTags>,
+    pub custom_tags: Vec<String>,
+    pub mint: Pubkey,
+}
+
+/// The original sprite account layout, tagged with [`Key::SpriteAccountV1`].
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct SpriteAccountV1 {
+    pub key: Key,
+    pub base_mint: Pubkey,
+    pub sprites: Vec<SpriteV1>,
+}
+
+/// Fields the original layout did not record are left at their defaults: timestamps are zero,
+/// sprites are unequipped in the default slot and have no known artist.
+impl From<SpriteAccountV1> for SpriteAccount {
+    fn from(account: SpriteAccountV1) -> Self {
+        Self {
+            key: Key::SpriteAccount,
+            base_mint: account.base_mint,
+            sprites: account
+                .sprites
+                .into_iter()
+                .map(|sprite| Sprite {
+                    name: sprite.name,
+                    description: sprite.description,
+                    perspective_tags: sprite.perspective_tags,
+                    style_tags: sprite.style_tags,
+                    custom_tags: sprite.custom_tags,
+                    mint: sprite.mint,
+                    ..Sprite::default()
+                })
+                .collect(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Size limits applied to sprites at store time.
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct ConfigLimits {
+    pub max_name_len: u16,
+    pub max_description_len: u16,
+    pub max_custom_tags: u16,
+    pub max_custom_tag_len: u16,
+    pub max_sprites_per_account: u16,
+}
+
+impl Default for ConfigLimits {
+    fn default() -> Self {
+        Self {
+            max_name_len: 32,
+            max_description_len: 256,
+            max_custom_tags: 16,
+            max_custom_tag_len: 32,
+            max_sprites_per_account: 64,
+        }
+    }
+}
+
+/// A fee in SPL tokens, charged on top of the lamport fee.
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct TokenFee {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Fees charged by `StoreSprite` and the treasury they are paid to.
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct FeeSchedule {
+    pub store_fee_lamports: u64,
+    pub token_fee: Option<TokenFee>,
+    /// Receives the lamport fee directly and the token fee through a token account it owns.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
+    pub treasury: Pubkey,
+    /// Payers that are never charged, such as game servers.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey::vec"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Vec<String>"))]
+    pub exempt_authorities: Vec<Pubkey>,
+}
+
+impl FeeSchedule {
+    pub fn is_exempt(&self, payer: &Pubkey) -> bool {
+        self.exempt_authorities.contains(payer)
+    }
+}
+
+/// Program-wide settings, stored in a singleton PDA. Until it is initialized the default limits
+/// apply and the program is not paused.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct ProgramConfig {
+    pub key: Key,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
+    pub admin: Pubkey,
+    /// The admin proposed by the current admin, who takes over once they accept.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey::option"))]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
+    pub pending_admin: Option<Pubkey>,
+    pub paused: bool,
+    pub limits: ConfigLimits,
+    pub fees: FeeSchedule,
+}
+
+impl SolanaAccount for ProgramConfig {
+    fn key() -> Key {
+        Key::ProgramConfig
+    }
+
+    fn size() -> usize {
+        const FEE_SCHEDULE_SIZE: usize = 8 + 41 + 32 + 4 + 32 * MAX_FEE_EXEMPT_AUTHORITIES;
+        1 + 32 + 33 + 1 + 10 + FEE_SCHEDULE_SIZE
+    }
 }
 
 pub trait SolanaAccount: BorshDeserialize {
@@ -73,7 +565,7 @@ pub trait SolanaAccount: BorshDeserialize {
     fn size() -> usize;
 
     fn is_correct_account_type(data: &[u8], data_type: Key) -> bool {
-        let key: Option<Key> = Key::from_u8(data[0]);
+        let key: Option<Key> = data.first().and_then(|key| Key::from_u8(*key));
         match key {
             Some(key) => key == data_type || key == Key::Uninitialized,
             None => false,
@@ -90,7 +582,7 @@ pub trait SolanaAccount: BorshDeserialize {
 
     fn safe_deserialize(mut data: &[u8]) -> Result<Self, BorshError> {
         if !Self::is_correct_account_type(data, Self::key()) {
-            return Err(BorshError::new(ErrorKind::Other, "DataTypeMismatch"));
+            return Err(BorshError::other("DataTypeMismatch"));
         }
 
         let result = Self::deserialize(&mut data)?;
@@ -0,0 +1,173 @@
+use borsh::BorshDeserialize;
+use solana_program::{instruction::Instruction, pubkey::Pubkey, system_program, sysvar};
+use sprite_manager::{
+    builders::{
+        CreateSpriteAccountBuilder, EquipSpriteBuilder, SetActiveVariantBuilder,
+        SetPaletteVariantBuilder, StoreSpriteBuilder, UnequipSpriteBuilder,
+    },
+    instruction::{SpriteManagerInstruction, StoreSpriteArgs},
+    state::{EquipmentSlot, FeeSchedule, TokenFee},
+};
+
+fn args() -> StoreSpriteArgs {
+    StoreSpriteArgs {
+        name: "sprite".to_string(),
+        description: "a sprite".to_string(),
+        slot: EquipmentSlot::Hat,
+        ..StoreSpriteArgs::default()
+    }
+}
+
+/// The addresses the builders should resolve, derived here from their raw seeds rather than
+/// with the program's `pda` helpers.
+struct Expected {
+    sprite_account: Pubkey,
+    escrow: Pubkey,
+    config: Pubkey,
+}
+
+impl Expected {
+    fn new(base_mint: &Pubkey) -> Self {
+        let program_id = sprite_manager::id();
+        let (sprite_account, _) =
+            Pubkey::find_program_address(&[b"sprite", base_mint.as_ref()], &program_id);
+        let (escrow, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                mpl_token_metadata::id().as_ref(),
+                base_mint.as_ref(),
+                &[1],
+                sprite_account.as_ref(),
+                b"escrow",
+            ],
+            &mpl_token_metadata::id(),
+        );
+        let (config, _) = Pubkey::find_program_address(&[b"config"], &program_id);
+        Self {
+            sprite_account,
+            escrow,
+            config,
+        }
+    }
+}
+
+fn metadata(mint: &Pubkey, suffix: &[&[u8]]) -> Pubkey {
+    let program_id = mpl_token_metadata::id();
+    let mut seeds: Vec<&[u8]> = vec![b"metadata", program_id.as_ref(), mint.as_ref()];
+    seeds.extend_from_slice(suffix);
+    Pubkey::find_program_address(&seeds, &program_id).0
+}
+
+fn ata(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), spl_token::id().as_ref(), mint.as_ref()],
+        &spl_associated_token_account::id(),
+    )
+    .0
+}
+
+/// Each account of `instruction` as `(pubkey, is_signer, is_writable)`.
+fn accounts(instruction: &Instruction) -> Vec<(Pubkey, bool, bool)> {
+    assert_eq!(instruction.program_id, sprite_manager::id());
+    instruction
+        .accounts
+        .iter()
+        .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+        .collect()
+}
+
+#[test]
+fn test_builders_resolve_accounts() {
+    let base_mint = Pubkey::new_unique();
+    let sprite_mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let expected = Expected::new(&base_mint);
+    let base_token_account = ata(&owner, &base_mint);
+
+    assert_eq!(
+        accounts(&CreateSpriteAccountBuilder::new(base_mint, owner).instruction()),
+        vec![
+            (expected.escrow, false, true),
+            (metadata(&base_mint, &[]), false, true),
+            (base_mint, false, false),
+            (base_token_account, false, false),
+            (metadata(&base_mint, &[b"edition"]), false, false),
+            (expected.sprite_account, false, true),
+            (owner, true, false),
+            (mpl_token_metadata::id(), false, false),
+            (system_program::id(), false, false),
+            (sysvar::instructions::id(), false, false),
+            (expected.config, false, false),
+        ]
+    );
+
+    // Every instruction a holder runs on a stored sprite starts with the same accounts.
+    let holder = [
+        (expected.sprite_account, false, true),
+        (base_mint, false, false),
+        (base_token_account, false, false),
+        (sprite_mint, false, false),
+    ];
+    let read_only_owner = [(owner, true, false), (expected.config, false, false)];
+    for instruction in [
+        EquipSpriteBuilder::new(base_mint, sprite_mint, owner).instruction(),
+        UnequipSpriteBuilder::new(base_mint, sprite_mint, owner).instruction(),
+        SetActiveVariantBuilder::new(base_mint, sprite_mint, owner).instruction(),
+    ] {
+        assert_eq!(
+            accounts(&instruction),
+            [&holder[..], &read_only_owner].concat()
+        );
+    }
+    assert_eq!(
+        accounts(&SetPaletteVariantBuilder::new(base_mint, sprite_mint, owner, None).instruction()),
+        [
+            &holder[..],
+            &[
+                (owner, true, true),
+                (system_program::id(), false, false),
+                (expected.config, false, false),
+            ],
+        ]
+        .concat()
+    );
+
+    let fee_mint = Pubkey::new_unique();
+    let fees = FeeSchedule {
+        store_fee_lamports: 1_000,
+        token_fee: Some(TokenFee {
+            mint: fee_mint,
+            amount: 10,
+        }),
+        treasury: Pubkey::new_unique(),
+        exempt_authorities: vec![],
+    };
+    let args = args();
+    let instruction = StoreSpriteBuilder::new(base_mint, sprite_mint, owner, args.clone())
+        .fees(&fees)
+        .instruction();
+    assert_eq!(
+        accounts(&instruction),
+        vec![
+            (expected.escrow, false, true),
+            (base_mint, false, false),
+            (sprite_mint, false, false),
+            (metadata(&sprite_mint, &[]), false, false),
+            (ata(&owner, &sprite_mint), false, true),
+            (ata(&expected.escrow, &sprite_mint), false, true),
+            (owner, true, true),
+            (expected.sprite_account, false, true),
+            (system_program::id(), false, false),
+            (spl_token::id(), false, false),
+            (spl_associated_token_account::id(), false, false),
+            (expected.config, false, false),
+            (fees.treasury, false, true),
+            (ata(&owner, &fee_mint), false, true),
+            (ata(&fees.treasury, &fee_mint), false, true),
+        ]
+    );
+    match SpriteManagerInstruction::try_from_slice(&instruction.data).unwrap() {
+        SpriteManagerInstruction::StoreSprite(decoded) => assert_eq!(decoded, args),
+        instruction => panic!("unexpected instruction {:?}", instruction),
+    }
+}
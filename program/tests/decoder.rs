@@ -0,0 +1,158 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use solana_sdk::message::Message;
+use sprite_manager::{
+    builders::{EquipSpriteBuilder, StoreSpriteBuilder},
+    decoder::{account_names, decode_compiled_instruction, decode_instruction},
+    instruction::{
+        accept_admin, create_sprite_account, initialize_config, migrate_sprite_account,
+        propose_admin, set_active_variant, set_palette_variant, unequip_sprite, update_config,
+        SpriteManagerInstruction, StoreSpriteArgs,
+    },
+    state::{ConfigLimits, EquipmentSlot, FeeSchedule, License, StyleTags, TokenFee},
+};
+
+fn store_args() -> StoreSpriteArgs {
+    StoreSpriteArgs {
+        name: "Straw hat".to_string(),
+        description: "A wide-brimmed hat".to_string(),
+        style_tags: vec![StyleTags::Pixel],
+        custom_tags: vec!["summer".to_string()],
+        attributes: vec![("rarity".to_string(), "epic".into())],
+        slot: EquipmentSlot::Hat,
+        z_order: 3,
+        license: License::Cc0,
+        ..StoreSpriteArgs::default()
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let mut words = name.split('_');
+    let mut camel = words.next().unwrap_or_default().to_string();
+    for word in words {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+#[test]
+fn test_account_names_match_idl() {
+    let idl: serde_json::Value =
+        serde_json::from_str(include_str!("../../packages/sdk/idl/sprite_manager.json")).unwrap();
+
+    let key = Pubkey::new_unique();
+    let instructions = vec![
+        create_sprite_account(&key, &key, &key, &key, &key, &key, &key, &key),
+        StoreSpriteBuilder::new(key, key, key, store_args()).instruction(),
+        EquipSpriteBuilder::new(key, key, key).instruction(),
+        unequip_sprite(&key, &key, &key, &key, &key, &key),
+        set_palette_variant(&key, &key, &key, &key, &key, &key, None),
+        set_active_variant(&key, &key, &key, &key, &key, &key),
+        initialize_config(&key, &key, ConfigLimits::default(), FeeSchedule::default()),
+        update_config(&key, &key, None, None, None),
+        propose_admin(&key, &key, None),
+        accept_admin(&key, &key),
+        migrate_sprite_account(&key, &key, &key, &key),
+    ];
+
+    let idl_instructions = idl["instructions"].as_array().unwrap();
+    assert_eq!(instructions.len(), idl_instructions.len());
+    for (instruction, idl_instruction) in instructions.iter().zip(idl_instructions) {
+        let decoded = decode_instruction(&instruction.data, &[key; 13]).unwrap();
+        assert_eq!(decoded.name(), idl_instruction["name"]);
+
+        let idl_names: Vec<&str> = idl_instruction["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|account| account["name"].as_str().unwrap())
+            .collect();
+        let names: Vec<String> = account_names(&decoded.instruction)
+            .iter()
+            .map(|name| camel_case(name))
+            .collect();
+        assert_eq!(names, idl_names, "accounts of {}", decoded.name());
+        assert_eq!(names.len(), instruction.accounts.len());
+    }
+}
+
+#[test]
+fn test_decode_store_sprite() {
+    let base_mint = Pubkey::new_unique();
+    let sprite_mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let fees = FeeSchedule {
+        store_fee_lamports: 0,
+        token_fee: Some(TokenFee {
+            mint: Pubkey::new_unique(),
+            amount: 1,
+        }),
+        treasury: Pubkey::new_unique(),
+        exempt_authorities: vec![],
+    };
+    let instruction = StoreSpriteBuilder::new(base_mint, sprite_mint, owner, store_args())
+        .fees(&fees)
+        .instruction();
+
+    let message = Message::new(std::slice::from_ref(&instruction), Some(&owner));
+    let decoded =
+        decode_compiled_instruction(&message.instructions[0], &message.account_keys).unwrap();
+
+    assert_eq!(decoded.name(), "StoreSprite");
+    match &decoded.instruction {
+        SpriteManagerInstruction::StoreSprite(args) => assert_eq!(*args, store_args()),
+        instruction => panic!("unexpected instruction {:?}", instruction),
+    }
+    assert_eq!(decoded.accounts.len(), 15);
+    for (decoded_account, meta) in decoded.accounts.iter().zip(&instruction.accounts) {
+        assert_eq!(decoded_account.pubkey, meta.pubkey);
+    }
+    assert_eq!(decoded.account("base_mint"), Some(&base_mint));
+    assert_eq!(decoded.account("payer"), Some(&owner));
+    assert_eq!(decoded.account("treasury"), Some(&fees.treasury));
+    assert_eq!(
+        decoded.account("fee_token_dst"),
+        Some(&instruction.accounts[14].pubkey)
+    );
+
+    let display = decoded.to_string();
+    assert!(display.starts_with("Sprite Manager: StoreSprite\n"));
+    assert!(display.contains("  Name: Straw hat\n"));
+    assert!(display.contains("  Slot: Hat (z-order 3)\n"));
+    assert!(display.contains("  rarity: epic\n"));
+    assert!(display.contains("  License: CC0\n"));
+    assert!(display.contains(&format!("  sprite_mint: {}", sprite_mint)));
+}
+
+#[test]
+fn test_decode_errors() {
+    let key = Pubkey::new_unique();
+    let instruction = EquipSpriteBuilder::new(key, key, key).instruction();
+
+    assert_eq!(
+        decode_instruction(&[], &[]).unwrap_err(),
+        ProgramError::InvalidInstructionData
+    );
+    assert_eq!(
+        decode_instruction(&[255], &[]).unwrap_err(),
+        ProgramError::InvalidInstructionData
+    );
+    assert_eq!(
+        decode_instruction(&instruction.data, &[key; 5]).unwrap_err(),
+        ProgramError::NotEnoughAccountKeys
+    );
+
+    let decoded = decode_instruction(&instruction.data, &[key; 7]).unwrap();
+    assert_eq!(decoded.accounts[6].name, "remaining");
+
+    let mut message = Message::new(&[instruction], Some(&key));
+    let program_id_index = message.instructions[0].program_id_index as usize;
+    message.account_keys[program_id_index] = Pubkey::new_unique();
+    assert_eq!(
+        decode_compiled_instruction(&message.instructions[0], &message.account_keys).unwrap_err(),
+        ProgramError::IncorrectProgramId
+    );
+}
@@ -0,0 +1,528 @@
+#![cfg(feature = "test-bpf")]
+
+//! Failure paths of every instruction, one test per error or attack.
+//!
+//! The size limit, pause, admin, slot, palette, attribute key, rendition and verified creator
+//! errors and the missing family error are covered by `sprite_manager.rs`, the layout migration
+//! errors by `migration.rs`. `FailedToSerialize`, `FailedToBorrowAccountData` and
+//! `NumericalOverflow` cannot be caused by a transaction.
+
+use borsh::BorshSerialize;
+use solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    account::AccountSharedData, signature::Keypair, signer::Signer, transaction::Transaction,
+};
+use sprite_manager::{
+    builders::{CreateSpriteAccountBuilder, EquipSpriteBuilder, StoreSpriteBuilder},
+    error::SpriteManagerError,
+    instruction::{initialize_config, unequip_sprite, StoreSpriteArgs},
+    pda::{find_config_address, find_sprite_address},
+    state::{
+        AttributeValue, ConfigLimits, EquipmentSlot, FeeSchedule, Key, ProgramConfig, SolanaAccount,
+    },
+};
+use sprite_manager_test_utils::*;
+
+/// Sends `instruction` signed and paid for by `signer`, or the context's payer.
+async fn process(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signer: Option<&Keypair>,
+) -> Result<(), BanksClientError> {
+    let signer = signer.unwrap_or(&context.payer);
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signer.pubkey()),
+        &[signer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Stores `sprite`, taken from its token account, in `fixture`'s sprite account as `owner`.
+fn store(fixture: &SpriteFixture, sprite: &Metadata, owner: &Pubkey) -> StoreSpriteBuilder {
+    StoreSpriteBuilder::new(
+        fixture.base.mint.pubkey(),
+        sprite.mint.pubkey(),
+        *owner,
+        sprite_args(fixture.sprites.len()),
+    )
+    .sprite_token_account(sprite.token.pubkey())
+}
+
+fn equip(fixture: &SpriteFixture, sprite_mint: &Pubkey, owner: &Pubkey) -> Instruction {
+    EquipSpriteBuilder::new(fixture.base.mint.pubkey(), *sprite_mint, *owner)
+        .base_token_account(fixture.base.token.pubkey())
+        .instruction()
+}
+
+#[tokio::test]
+async fn test_wrong_sprite_pda() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::new(&mut context, 1).await;
+    let other = SpriteFixture::new(&mut context, 1).await;
+
+    // The other base NFT's sprite account, passed for this base NFT.
+    let mut instruction = equip(
+        &fixture,
+        &fixture.sprites[0].mint.pubkey(),
+        &context.payer.pubkey(),
+    );
+    instruction.accounts[0].pubkey = other.sprite_account;
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::DerivedKeyInvalid);
+
+    let (sprite, _, _) = create_nft(&mut context, false, None).await;
+    let mut instruction = store(&fixture, &sprite, &context.payer.pubkey()).instruction();
+    instruction.accounts[7].pubkey = other.sprite_account;
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::DerivedKeyInvalid);
+
+    fixture.assert_stored(&mut context).await;
+    other.assert_stored(&mut context).await;
+}
+
+#[tokio::test]
+async fn test_wrong_escrow() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::empty(&mut context).await;
+    let other = SpriteFixture::empty(&mut context).await;
+    let (sprite, _, _) = create_nft(&mut context, false, None).await;
+
+    // Depositing into another base NFT's escrow while recording the sprite here.
+    let mut instruction = store(&fixture, &sprite, &context.payer.pubkey()).instruction();
+    instruction.accounts[0].pubkey = other.escrow;
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::DerivedKeyInvalid);
+
+    fixture.assert_stored(&mut context).await;
+    let account = get_account(&mut context, &sprite.token.pubkey()).await;
+    assert_eq!(
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount,
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_wrong_escrow_token_account() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::empty(&mut context).await;
+    let (sprite, _, _) = create_nft(&mut context, false, None).await;
+
+    // An existing token account as the destination would skip creating the escrow's ATA, so the
+    // sprite would be recorded while the depositor keeps it.
+    let mut instruction = store(&fixture, &sprite, &context.payer.pubkey()).instruction();
+    instruction.accounts[5].pubkey = sprite.token.pubkey();
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::DerivedKeyInvalid);
+
+    fixture.assert_stored(&mut context).await;
+    let account = get_account(&mut context, &sprite.token.pubkey()).await;
+    assert_eq!(
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount,
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_sprite_already_stored() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::new(&mut context, 1).await;
+
+    // The escrow's token account already exists, so a second store must not record the mint again.
+    let instruction = store(&fixture, &fixture.sprites[0], &context.payer.pubkey()).instruction();
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::SpriteAlreadyStored);
+
+    fixture.assert_stored(&mut context).await;
+}
+
+#[tokio::test]
+async fn test_reinitialized_sprite_account() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::new(&mut context, 1).await;
+    let before = get_account(&mut context, &fixture.sprite_account).await;
+
+    let instruction =
+        CreateSpriteAccountBuilder::new(fixture.base.mint.pubkey(), context.payer.pubkey())
+            .base_token_account(fixture.base.token.pubkey())
+            .instruction();
+    // The fixture sent the same transaction, so move on to a new blockhash before resending it.
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::AlreadyInitialized);
+
+    assert_eq!(
+        get_account(&mut context, &fixture.sprite_account).await,
+        before
+    );
+}
+
+#[tokio::test]
+async fn test_reinitialized_config() {
+    let mut context = program_test().start_with_context().await;
+    let admin = context.payer.pubkey();
+    set_upgrade_authority(&mut context, &admin);
+    process(
+        &mut context,
+        initialize_config(
+            &sprite_manager::id(),
+            &admin,
+            ConfigLimits::default(),
+            FeeSchedule::default(),
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+
+    // A later upgrade authority tries to take over the config by initializing it again.
+    let attacker = Keypair::new();
+    airdrop(&mut context, &attacker.pubkey(), 1_000_000_000)
+        .await
+        .unwrap();
+    set_upgrade_authority(&mut context, &attacker.pubkey());
+    let err = process(
+        &mut context,
+        initialize_config(
+            &sprite_manager::id(),
+            &attacker.pubkey(),
+            ConfigLimits::default(),
+            FeeSchedule::default(),
+        ),
+        Some(&attacker),
+    )
+    .await
+    .unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::ConfigAlreadyInitialized);
+
+    let config = get_account(&mut context, &find_config_address().0).await;
+    let config = ProgramConfig::safe_deserialize(&config.data).unwrap();
+    assert_eq!(config.admin, admin);
+}
+
+#[tokio::test]
+async fn test_non_authority_cannot_initialize_config() {
+    let mut context = program_test().start_with_context().await;
+    let authority = Keypair::new();
+    set_upgrade_authority(&mut context, &authority.pubkey());
+
+    // Anyone watching the deployment tries to claim the config before the deployer does.
+    let attacker = Keypair::new();
+    airdrop(&mut context, &attacker.pubkey(), 1_000_000_000)
+        .await
+        .unwrap();
+    let err = process(
+        &mut context,
+        initialize_config(
+            &sprite_manager::id(),
+            &attacker.pubkey(),
+            ConfigLimits::default(),
+            FeeSchedule::default(),
+        ),
+        Some(&attacker),
+    )
+    .await
+    .unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::NotUpgradeAuthority);
+
+    let config = context
+        .banks_client
+        .get_account(find_config_address().0)
+        .await
+        .unwrap();
+    assert!(config.is_none());
+}
+
+#[tokio::test]
+async fn test_wrong_owner() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::new(&mut context, 1).await;
+    let sprite_mint = fixture.sprites[0].mint.pubkey();
+
+    // A token account that is not owned by the token program.
+    let mut instruction = equip(&fixture, &sprite_mint, &context.payer.pubkey());
+    instruction.accounts[2].pubkey = fixture.base.pubkey;
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::IncorrectOwner);
+
+    // A copy of the sprite account at the PDA of a base NFT without one, owned by another program.
+    let (base, _, _) = create_nft(&mut context, false, None).await;
+    let mut sprite_account = fixture.get_data(&mut context).await;
+    sprite_account.base_mint = base.mint.pubkey();
+    let data = sprite_account.try_to_vec().unwrap();
+    let mut forged = AccountSharedData::new(1_000_000_000, data.len(), &Pubkey::new_unique());
+    forged.set_data(data);
+    context.set_account(&find_sprite_address(&base.mint.pubkey()).0, &forged);
+
+    let instruction =
+        EquipSpriteBuilder::new(base.mint.pubkey(), sprite_mint, context.payer.pubkey())
+            .base_token_account(base.token.pubkey())
+            .instruction();
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::IncorrectOwner);
+}
+
+#[tokio::test]
+async fn test_data_type_mismatch() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::new(&mut context, 1).await;
+
+    // A program config where the base NFT's sprite account should be.
+    let (base, _, _) = create_nft(&mut context, false, None).await;
+    let config = ProgramConfig {
+        key: Key::ProgramConfig,
+        ..ProgramConfig::default()
+    };
+    let mut data = config.try_to_vec().unwrap();
+    ProgramConfig::pad_length(&mut data).unwrap();
+    let mut forged = AccountSharedData::new(1_000_000_000, data.len(), &sprite_manager::id());
+    forged.set_data(data);
+    context.set_account(&find_sprite_address(&base.mint.pubkey()).0, &forged);
+
+    let instruction = EquipSpriteBuilder::new(
+        base.mint.pubkey(),
+        fixture.sprites[0].mint.pubkey(),
+        context.payer.pubkey(),
+    )
+    .base_token_account(base.token.pubkey())
+    .instruction();
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::DataTypeMismatch);
+}
+
+#[tokio::test]
+async fn test_non_holder_cannot_equip() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::new(&mut context, 1).await;
+    let sprite_mint = fixture.sprites[0].mint.pubkey();
+    let attacker = Keypair::new();
+    airdrop(&mut context, &attacker.pubkey(), 1_000_000_000)
+        .await
+        .unwrap();
+
+    let err = process(
+        &mut context,
+        equip(&fixture, &sprite_mint, &attacker.pubkey()),
+        Some(&attacker),
+    )
+    .await
+    .unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::NotBaseTokenHolder);
+
+    let sprite_account = fixture.get_data(&mut context).await;
+    assert!(!sprite_account.find_sprite(&sprite_mint).unwrap().equipped);
+}
+
+#[tokio::test]
+async fn test_non_holder_cannot_store() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::empty(&mut context).await;
+    let (sprite, _, _) = create_nft(&mut context, false, None).await;
+    let attacker = Keypair::new();
+    airdrop(&mut context, &attacker.pubkey(), 1_000_000_000)
+        .await
+        .unwrap();
+
+    // The attacker moves the payer's sprite into the escrow on their own authority.
+    let instruction = store(&fixture, &sprite, &attacker.pubkey()).instruction();
+    let err = process(&mut context, instruction, Some(&attacker))
+        .await
+        .unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::InvalidTokenAccount);
+
+    fixture.assert_stored(&mut context).await;
+    let account = get_account(&mut context, &sprite.token.pubkey()).await;
+    assert_eq!(
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount,
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_sprite_not_found() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::new(&mut context, 1).await;
+    let (unstored, _, _) = create_nft(&mut context, false, None).await;
+
+    let instruction = equip(&fixture, &unstored.mint.pubkey(), &context.payer.pubkey());
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::SpriteNotFound);
+}
+
+#[tokio::test]
+async fn test_sprite_not_equipped() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::new(&mut context, 1).await;
+    let before = get_account(&mut context, &fixture.sprite_account).await;
+
+    let instruction = unequip_sprite(
+        &sprite_manager::id(),
+        &fixture.sprite_account,
+        &fixture.base.mint.pubkey(),
+        &fixture.base.token.pubkey(),
+        &fixture.sprites[0].mint.pubkey(),
+        &context.payer.pubkey(),
+    );
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::SpriteNotEquipped);
+
+    assert_eq!(
+        get_account(&mut context, &fixture.sprite_account).await,
+        before
+    );
+}
+
+#[tokio::test]
+async fn test_equip_inactive_variant() {
+    let mut context = program_test().start_with_context().await;
+    let mut fixture = SpriteFixture::empty(&mut context).await;
+    let family = Pubkey::new_unique();
+    // The variants are worn in different slots, so only the family keeps them apart.
+    for (index, slot) in [EquipmentSlot::Body, EquipmentSlot::Hat]
+        .into_iter()
+        .enumerate()
+    {
+        fixture
+            .store(
+                &mut context,
+                StoreSpriteArgs {
+                    family: Some(family),
+                    slot,
+                    ..sprite_args(index)
+                },
+            )
+            .await
+            .unwrap();
+    }
+    let (active, inactive) = (
+        fixture.sprites[0].mint.pubkey(),
+        fixture.sprites[1].mint.pubkey(),
+    );
+    let owner = context.payer.pubkey();
+    process(&mut context, equip(&fixture, &active, &owner), None)
+        .await
+        .unwrap();
+
+    // Wearing a second variant of the family would draw both at once.
+    let err = process(&mut context, equip(&fixture, &inactive, &owner), None)
+        .await
+        .unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::VariantNotActive);
+
+    let loadout: Vec<Pubkey> = fixture
+        .get_data(&mut context)
+        .await
+        .loadout()
+        .iter()
+        .map(|sprite| sprite.mint)
+        .collect();
+    assert_eq!(loadout, vec![active]);
+}
+
+#[tokio::test]
+async fn test_metadata_mint_mismatch() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::empty(&mut context).await;
+    let (sprite, _, _) = create_nft(&mut context, false, None).await;
+
+    // Metadata of another NFT, e.g. to claim its verified creator as the artist.
+    let mut instruction = store(&fixture, &sprite, &context.payer.pubkey()).instruction();
+    instruction.accounts[3].pubkey = fixture.base.pubkey;
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::MetadataMintMismatch);
+
+    fixture.assert_stored(&mut context).await;
+}
+
+#[tokio::test]
+async fn test_invalid_attributes() {
+    let mut context = program_test().start_with_context().await;
+    let mut fixture = SpriteFixture::empty(&mut context).await;
+
+    let err = fixture
+        .store(
+            &mut context,
+            StoreSpriteArgs {
+                attributes: vec![(String::new(), AttributeValue::Integer(1))],
+                ..sprite_args(0)
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::InvalidAttributes);
+
+    fixture.assert_stored(&mut context).await;
+}
+
+#[tokio::test]
+async fn test_invalid_treasury() {
+    let mut context = program_test().start_with_context().await;
+    let fees = FeeSchedule {
+        store_fee_lamports: 5_000,
+        treasury: Pubkey::new_unique(),
+        ..FeeSchedule::default()
+    };
+    let admin = context.payer.pubkey();
+    set_upgrade_authority(&mut context, &admin);
+    process(
+        &mut context,
+        initialize_config(
+            &sprite_manager::id(),
+            &admin,
+            ConfigLimits::default(),
+            fees.clone(),
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+    let fixture = SpriteFixture::empty(&mut context).await;
+    let (sprite, _, _) = create_nft(&mut context, false, None).await;
+
+    // The fee is paid to an account of the payer's choosing.
+    let diverted = FeeSchedule {
+        treasury: Pubkey::new_unique(),
+        ..fees
+    };
+    let instruction = store(&fixture, &sprite, &context.payer.pubkey())
+        .fees(&diverted)
+        .instruction();
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::InvalidTreasury);
+
+    fixture.assert_stored(&mut context).await;
+    assert!(context
+        .banks_client
+        .get_account(diverted.treasury)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_invalid_token_account() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::empty(&mut context).await;
+    let (sprite, _, _) = create_nft(&mut context, false, None).await;
+
+    // The base NFT's token account holds a token, but not the sprite's.
+    let instruction = store(&fixture, &sprite, &context.payer.pubkey())
+        .sprite_token_account(fixture.base.token.pubkey())
+        .instruction();
+    let err = process(&mut context, instruction, None).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::InvalidTokenAccount);
+
+    fixture.assert_stored(&mut context).await;
+}
@@ -0,0 +1,55 @@
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+use sprite_manager::{
+    events::{parse_events, SpriteEvent},
+    state::EquipmentSlot,
+};
+
+fn data_line(event: &SpriteEvent) -> String {
+    format!(
+        "Program data: {}",
+        base64::encode(event.try_to_vec().unwrap())
+    )
+}
+
+#[test]
+fn test_parse_events() {
+    let program_id = sprite_manager::id();
+    let other_program = Pubkey::new_unique();
+    let stored = SpriteEvent::SpriteStored {
+        base_mint: Pubkey::new_unique(),
+        sprite_mint: Pubkey::new_unique(),
+        slot: EquipmentSlot::Hat,
+    };
+    let fee = SpriteEvent::FeeCharged {
+        payer: Pubkey::new_unique(),
+        treasury: Pubkey::new_unique(),
+        lamports: 5000,
+        token_fee: None,
+    };
+    let foreign = SpriteEvent::SpriteEquipped {
+        base_mint: Pubkey::new_unique(),
+        sprite_mint: Pubkey::new_unique(),
+    };
+
+    let logs = vec![
+        format!("Program {} invoke [1]", other_program),
+        data_line(&foreign),
+        format!("Program {} success", other_program),
+        format!("Program {} invoke [1]", program_id),
+        format!("Program {} invoke [2]", spl_token::id()),
+        data_line(&foreign),
+        format!("Program {} success", spl_token::id()),
+        data_line(&fee),
+        "Program data: not-base64".to_string(),
+        data_line(&stored),
+        format!(
+            "Program {} consumed 21000 of 200000 compute units",
+            program_id
+        ),
+        format!("Program {} success", program_id),
+        data_line(&foreign),
+    ];
+
+    assert_eq!(parse_events(&logs), vec![fee, stored]);
+}
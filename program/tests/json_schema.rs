@@ -0,0 +1,38 @@
+#![cfg(feature = "json-schema")]
+
+//! Checks the published JSON Schemas in `program/schema` against the state types. Run with
+//! `UPDATE_SCHEMA=1` to rewrite them after changing the account format.
+
+use std::{fs, path::PathBuf};
+
+use schemars::{schema::RootSchema, schema_for};
+use sprite_manager::state::{ProgramConfig, SpriteAccount};
+
+fn check_schema(file_name: &str, schema: RootSchema) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("schema")
+        .join(file_name);
+    let generated = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+
+    if std::env::var_os("UPDATE_SCHEMA").is_some() {
+        fs::write(&path, generated).unwrap();
+        return;
+    }
+
+    let published = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        published, generated,
+        "{} is out of date, rerun with UPDATE_SCHEMA=1",
+        file_name
+    );
+}
+
+#[test]
+fn test_sprite_account_schema() {
+    check_schema("sprite_account.json", schema_for!(SpriteAccount));
+}
+
+#[test]
+fn test_program_config_schema() {
+    check_schema("program_config.json", schema_for!(ProgramConfig));
+}
@@ -0,0 +1,150 @@
+//! Sprite accounts written in the original layout, before `Key::SpriteAccount` moved to a new
+//! discriminator.
+
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+use sprite_manager::state::{
+    Key, PerspectiveTags, SolanaAccount, SpriteAccount, SpriteAccountV1, SpriteV1, StyleTags,
+};
+#[cfg(feature = "test-bpf")]
+use {
+    solana_program_test::*,
+    solana_sdk::{
+        account::AccountSharedData, instruction::Instruction, signer::Signer,
+        transaction::Transaction,
+    },
+    sprite_manager::{
+        builders::EquipSpriteBuilder, error::SpriteManagerError,
+        instruction::migrate_sprite_account,
+    },
+    sprite_manager_test_utils::*,
+};
+
+fn original_account(base_mint: Pubkey, sprite_mint: Pubkey) -> SpriteAccountV1 {
+    SpriteAccountV1 {
+        key: Key::SpriteAccountV1,
+        base_mint,
+        sprites: vec![SpriteV1 {
+            name: "Straw hat".to_string(),
+            description: "A wide-brimmed hat".to_string(),
+            perspective_tags: vec![PerspectiveTags::TopDown],
+            style_tags: vec![StyleTags::Pixel],
+            custom_tags: vec!["summer".to_string()],
+            mint: sprite_mint,
+        }],
+    }
+}
+
+#[test]
+fn test_original_layout_is_read_in_the_current_one() {
+    let base_mint = Pubkey::new_unique();
+    let sprite_mint = Pubkey::new_unique();
+    let data = original_account(base_mint, sprite_mint)
+        .try_to_vec()
+        .unwrap();
+    assert!(SpriteAccount::has_sprite_account_key(&data));
+
+    let account = SpriteAccount::safe_deserialize(&data).unwrap();
+    assert_eq!(account.key, Key::SpriteAccount);
+    assert_eq!(account.base_mint, base_mint);
+    assert!(account.families.is_empty());
+    let sprite = account.find_sprite(&sprite_mint).unwrap();
+    assert_eq!(sprite.name, "Straw hat");
+    assert_eq!(sprite.description, "A wide-brimmed hat");
+    assert_eq!(sprite.perspective_tags, vec![PerspectiveTags::TopDown]);
+    assert_eq!(sprite.style_tags, vec![StyleTags::Pixel]);
+    assert_eq!(sprite.custom_tags, vec!["summer".to_string()]);
+    assert!(!sprite.equipped);
+
+    // Written back, the account carries the current key and reads the same.
+    let migrated = account.try_to_vec().unwrap();
+    assert_eq!(migrated[0], Key::SpriteAccount as u8);
+    assert!(migrated.len() > data.len());
+    let reread = SpriteAccount::safe_deserialize(&migrated).unwrap();
+    assert_eq!(reread.find_sprite(&sprite_mint).unwrap().name, "Straw hat");
+}
+
+#[test]
+fn test_other_keys_are_not_sprite_accounts() {
+    let mut data = original_account(Pubkey::new_unique(), Pubkey::new_unique())
+        .try_to_vec()
+        .unwrap();
+    data[0] = Key::ProgramConfig as u8;
+    assert!(!SpriteAccount::has_sprite_account_key(&data));
+    assert!(SpriteAccount::safe_deserialize(&data).is_err());
+}
+
+#[cfg(feature = "test-bpf")]
+async fn process(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+) -> Result<(), BanksClientError> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_migrate_sprite_account() {
+    let mut context = program_test().start_with_context().await;
+    let fixture = SpriteFixture::new(&mut context, 1).await;
+    let base_mint = fixture.base.mint.pubkey();
+    let sprite_mint = fixture.sprites[0].mint.pubkey();
+    let payer = context.payer.pubkey();
+
+    // Rewrite the sprite account as the original program would have left it.
+    let data = original_account(base_mint, sprite_mint)
+        .try_to_vec()
+        .unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let mut original = AccountSharedData::new(
+        rent.minimum_balance(data.len()),
+        data.len(),
+        &sprite_manager::id(),
+    );
+    original.set_data(data);
+    context.set_account(&fixture.sprite_account, &original);
+
+    // Equipping cannot grow the account, so it has to be migrated first.
+    let equip = EquipSpriteBuilder::new(base_mint, sprite_mint, payer)
+        .base_token_account(fixture.base.token.pubkey())
+        .instruction();
+    let err = process(&mut context, equip.clone()).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::SpriteAccountNotMigrated);
+
+    let migrate = migrate_sprite_account(
+        &sprite_manager::id(),
+        &fixture.sprite_account,
+        &base_mint,
+        &payer,
+    );
+    process(&mut context, migrate.clone()).await.unwrap();
+
+    let account = get_account(&mut context, &fixture.sprite_account).await;
+    assert_eq!(account.data[0], Key::SpriteAccount as u8);
+    assert!(account.lamports >= rent.minimum_balance(account.data.len()));
+    let sprite_account = fixture.get_data(&mut context).await;
+    assert_eq!(sprite_account.base_mint, base_mint);
+    assert_eq!(
+        sprite_account.find_sprite(&sprite_mint).unwrap().name,
+        "Straw hat"
+    );
+
+    // Both instructions were sent before, so move on to a new blockhash before resending them.
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let err = process(&mut context, migrate).await.unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::SpriteAccountAlreadyMigrated);
+
+    process(&mut context, equip).await.unwrap();
+    let sprite_account = fixture.get_data(&mut context).await;
+    assert!(sprite_account.find_sprite(&sprite_mint).unwrap().equipped);
+}
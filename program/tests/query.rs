@@ -0,0 +1,123 @@
+use solana_program::pubkey::Pubkey;
+use sprite_manager::{
+    query::SpriteQuery,
+    state::{Key, PerspectiveTags, Sprite, SpriteAccount, StyleTags},
+};
+
+fn sprite(
+    name: &str,
+    description: &str,
+    perspective_tags: Vec<PerspectiveTags>,
+    style_tags: Vec<StyleTags>,
+    custom_tags: &[&str],
+) -> Sprite {
+    Sprite {
+        name: name.to_string(),
+        description: description.to_string(),
+        perspective_tags,
+        style_tags,
+        custom_tags: custom_tags.iter().map(|tag| tag.to_string()).collect(),
+        mint: Pubkey::new_unique(),
+        ..Sprite::default()
+    }
+}
+
+fn sprite_account() -> SpriteAccount {
+    SpriteAccount {
+        key: Key::SpriteAccount,
+        base_mint: Pubkey::new_unique(),
+        sprites: vec![
+            sprite(
+                "Straw Hat",
+                "A wide-brimmed hat",
+                vec![PerspectiveTags::TopDown],
+                vec![StyleTags::Pixel],
+                &["summer"],
+            ),
+            sprite(
+                "Knight armor",
+                "Heavy plate",
+                vec![PerspectiveTags::RPG, PerspectiveTags::TopDown],
+                vec![StyleTags::Pixel],
+                &[],
+            ),
+            sprite(
+                "Cape",
+                "Flows behind a HAT-wearing hero",
+                vec![PerspectiveTags::SideScroller],
+                vec![StyleTags::HandDrawn],
+                &["summer", "retired"],
+            ),
+        ],
+        ..SpriteAccount::default()
+    }
+}
+
+fn names(account: &SpriteAccount, query: &SpriteQuery) -> Vec<String> {
+    account
+        .query(query)
+        .map(|sprite| sprite.name.clone())
+        .collect()
+}
+
+#[test]
+fn test_tag_queries() {
+    let account = sprite_account();
+
+    assert_eq!(
+        names(
+            &account,
+            &SpriteQuery::Perspective(PerspectiveTags::TopDown)
+        ),
+        ["Straw Hat", "Knight armor"]
+    );
+    assert_eq!(
+        names(&account, &SpriteQuery::Style(StyleTags::HandDrawn)),
+        ["Cape"]
+    );
+    assert_eq!(
+        names(&account, &SpriteQuery::custom_tag("summer")),
+        ["Straw Hat", "Cape"]
+    );
+    assert!(names(&account, &SpriteQuery::custom_tag("Summer")).is_empty());
+}
+
+#[test]
+fn test_text_queries() {
+    let account = sprite_account();
+
+    assert_eq!(
+        names(&account, &SpriteQuery::text("hat")),
+        ["Straw Hat", "Cape"]
+    );
+    assert_eq!(
+        names(&account, &SpriteQuery::text("PLATE")),
+        ["Knight armor"]
+    );
+    assert!(names(&account, &SpriteQuery::text("sword")).is_empty());
+}
+
+#[test]
+fn test_combined_queries() {
+    let account = sprite_account();
+
+    let pixel_top_down = SpriteQuery::Style(StyleTags::Pixel)
+        & SpriteQuery::Perspective(PerspectiveTags::TopDown)
+        & !SpriteQuery::Perspective(PerspectiveTags::RPG);
+    assert_eq!(names(&account, &pixel_top_down), ["Straw Hat"]);
+
+    let any_style =
+        SpriteQuery::Style(StyleTags::Vector) | SpriteQuery::Style(StyleTags::HandDrawn);
+    assert_eq!(names(&account, &any_style), ["Cape"]);
+
+    let summer = SpriteQuery::custom_tag("summer") & !SpriteQuery::custom_tag("retired");
+    assert_eq!(names(&account, &summer), ["Straw Hat"]);
+
+    assert_eq!(names(&account, &SpriteQuery::all([])).len(), 3);
+    assert!(names(&account, &SpriteQuery::any([])).is_empty());
+    assert_eq!(
+        !!SpriteQuery::text("hat"),
+        SpriteQuery::text("hat"),
+        "double negation should cancel out"
+    );
+}
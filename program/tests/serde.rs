@@ -0,0 +1,170 @@
+#![cfg(feature = "serde")]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+use sprite_manager::state::{
+    AttributeValue, ConfigLimits, EquipmentSlot, FeeSchedule, Key, License, Palette,
+    PaletteVariant, PerspectiveTags, ProgramConfig, Rendition, RenditionFormat, Rgba, Sprite,
+    SpriteAccount, SpriteFamily, StyleTags, Timestamp, TokenFee,
+};
+
+fn sprite_account() -> SpriteAccount {
+    let family = Pubkey::new_unique();
+    let created_at = Timestamp {
+        unix_timestamp: 1_666_000_000,
+        slot: 42,
+    };
+    let sprite = Sprite {
+        name: "Straw hat".to_string(),
+        description: "A wide-brimmed hat".to_string(),
+        perspective_tags: vec![PerspectiveTags::TopDown],
+        style_tags: vec![StyleTags::Pixel],
+        custom_tags: vec!["summer".to_string()],
+        attributes: vec![
+            ("rarity".to_string(), "epic".into()),
+            ("season".to_string(), 3.into()),
+            ("animated".to_string(), false.into()),
+        ],
+        mint: Pubkey::new_unique(),
+        slot: EquipmentSlot::Hat,
+        z_order: 3,
+        equipped: true,
+        palette: Some(Palette {
+            colors: vec![Rgba {
+                r: 255,
+                g: 200,
+                b: 0,
+                a: 255,
+            }],
+            variants: vec![PaletteVariant {
+                name: "night".to_string(),
+                colors: vec![Rgba {
+                    r: 20,
+                    g: 20,
+                    b: 80,
+                    a: 255,
+                }],
+            }],
+        }),
+        palette_variant: Some("night".to_string()),
+        artist: Pubkey::new_unique(),
+        license: License::CustomUri("https://example.com/license".to_string()),
+        attribution: Some("Jane Artist".to_string()),
+        family: Some(family),
+        renditions: vec![Rendition {
+            uri: "https://example.com/hat@4x.png".to_string(),
+            scale: 4,
+            width: 64,
+            height: 64,
+            format: RenditionFormat::Png,
+            content_hash: [7; 32],
+        }],
+        created_at,
+        updated_at: created_at,
+    };
+
+    SpriteAccount {
+        key: Key::SpriteAccount,
+        base_mint: Pubkey::new_unique(),
+        created_at,
+        updated_at: created_at,
+        families: vec![SpriteFamily {
+            id: family,
+            active_variant: sprite.mint,
+            variant_count: 1,
+        }],
+        sprites: vec![sprite],
+    }
+}
+
+#[test]
+fn test_sprite_account_round_trip() {
+    let account = sprite_account();
+    let borsh = account.try_to_vec().unwrap();
+
+    let decoded = SpriteAccount::try_from_slice(&borsh).unwrap();
+    let json = serde_json::to_string(&decoded).unwrap();
+    let from_json: SpriteAccount = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(from_json.try_to_vec().unwrap(), borsh);
+}
+
+#[test]
+fn test_sprite_account_json_format() {
+    let account = sprite_account();
+    let json = serde_json::to_value(&account).unwrap();
+    let sprite = &json["sprites"][0];
+
+    assert_eq!(json["key"], "SpriteAccount");
+    assert_eq!(json["base_mint"], account.base_mint.to_string());
+    assert_eq!(sprite["mint"], account.sprites[0].mint.to_string());
+    assert_eq!(sprite["family"], account.families[0].id.to_string());
+    assert_eq!(sprite["slot"], "Hat");
+    assert_eq!(sprite["perspective_tags"][0], "TopDown");
+    assert_eq!(sprite["style_tags"][0], "Pixel");
+    assert_eq!(sprite["renditions"][0]["format"], "Png");
+    assert_eq!(
+        sprite["attributes"],
+        serde_json::json!([["rarity", "epic"], ["season", 3], ["animated", false]])
+    );
+    assert_eq!(
+        sprite["license"],
+        serde_json::json!({ "CustomUri": "https://example.com/license" })
+    );
+}
+
+#[test]
+fn test_program_config_round_trip() {
+    let config = ProgramConfig {
+        key: Key::ProgramConfig,
+        admin: Pubkey::new_unique(),
+        pending_admin: Some(Pubkey::new_unique()),
+        paused: true,
+        limits: ConfigLimits::default(),
+        fees: FeeSchedule {
+            store_fee_lamports: 5_000,
+            token_fee: Some(TokenFee {
+                mint: Pubkey::new_unique(),
+                amount: 10,
+            }),
+            treasury: Pubkey::new_unique(),
+            exempt_authorities: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        },
+    };
+    let borsh = config.try_to_vec().unwrap();
+
+    let json = serde_json::to_value(&config).unwrap();
+    assert_eq!(
+        json["pending_admin"],
+        config.pending_admin.unwrap().to_string()
+    );
+    assert_eq!(
+        json["fees"]["exempt_authorities"][1],
+        config.fees.exempt_authorities[1].to_string()
+    );
+
+    let from_json: ProgramConfig = serde_json::from_value(json).unwrap();
+    assert_eq!(from_json.try_to_vec().unwrap(), borsh);
+}
+
+#[test]
+fn test_rejects_invalid_pubkey() {
+    let mut json = serde_json::to_value(sprite_account()).unwrap();
+    json["base_mint"] = "not a pubkey".into();
+
+    assert!(serde_json::from_value::<SpriteAccount>(json).is_err());
+}
+
+#[test]
+fn test_attribute_values_are_untagged() {
+    let values: Vec<AttributeValue> = serde_json::from_str(r#"["epic", 3, true]"#).unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            AttributeValue::String("epic".to_string()),
+            AttributeValue::Integer(3),
+            AttributeValue::Bool(true),
+        ]
+    );
+}
@@ -1,15 +1,26 @@
 #![cfg(feature = "test-bpf")]
 
-pub mod utils;
-
+use solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey};
 use solana_program_test::*;
-use solana_sdk::{signer::Signer, transaction::Transaction};
-use sprite_manager::instruction::*;
-use utils::*;
+use solana_sdk::{account::Account, signature::Keypair, signer::Signer, transaction::Transaction};
+use sprite_manager::{
+    builders::{
+        EquipSpriteBuilder, SetActiveVariantBuilder, SetPaletteVariantBuilder, StoreSpriteBuilder,
+        UnequipSpriteBuilder,
+    },
+    error::SpriteManagerError,
+    instruction::*,
+    pda::find_config_address,
+};
+use sprite_manager_test_utils::*;
 
 mod sprite_manager_test {
     use solana_program::borsh::try_from_slice_unchecked;
-    use sprite_manager::state::SpriteAccount;
+    use sprite_manager::state::{
+        AttributeValue, ConfigLimits, EquipmentSlot, FeeSchedule, Key, License, Palette,
+        PaletteVariant, ProgramConfig, Rendition, RenditionFormat, Rgba, Sprite, SpriteAccount,
+        TokenFee,
+    };
 
     use super::*;
 
@@ -18,12 +29,12 @@ mod sprite_manager_test {
         let mut context = program_test().start_with_context().await;
 
         let payer_pubkey = context.payer.pubkey().to_owned();
-        let (metadata, master_edition, test_collection) =
+        let (metadata, _master_edition, test_collection) =
             create_nft(&mut context, true, Some(payer_pubkey)).await;
         let _test_collection = test_collection.expect("test collection should exist");
 
         let (sprite_manager_addr, escrow_addr) =
-            create_sprite_account_helper(&mut context, &metadata, &master_edition).await;
+            create_sprite_account_helper(&mut context, &metadata).await;
 
         let sprite_manager_account = context
             .banks_client
@@ -34,7 +45,15 @@ mod sprite_manager_test {
 
         let sprite_manager_account_data: SpriteAccount =
             try_from_slice_unchecked(&sprite_manager_account.data).expect("should deserialize");
-        println!("sprite_account: {:#?}", sprite_manager_account_data);
+        assert_eq!(sprite_manager_account_data.key, Key::SpriteAccount);
+        assert_eq!(
+            sprite_manager_account_data.base_mint,
+            metadata.mint.pubkey()
+        );
+        assert!(sprite_manager_account_data.sprites.is_empty());
+        assert_eq!(sprite_manager_account.owner, sprite_manager::id());
+        let escrow = get_account(&mut context, &escrow_addr).await;
+        assert_eq!(escrow.owner, mpl_token_metadata::id());
 
         // Build the sprite
         let (sprite_metadata, _sprite_master_edition, _) =
@@ -50,15 +69,21 @@ mod sprite_manager_test {
             &escrow_addr,
             &metadata.mint.pubkey(),
             &sprite_metadata.mint.pubkey(),
+            &sprite_metadata.pubkey,
             &sprite_metadata.token.pubkey(),
             &sprite_token_account,
             &context.payer.pubkey(),
             &sprite_manager_addr,
-            "test".to_string(),
-            "a test".to_string(),
-            vec![],
-            vec![],
-            vec!["test".to_string()],
+            &context.payer.pubkey(),
+            None,
+            StoreSpriteArgs {
+                name: "test".to_string(),
+                description: "a test".to_string(),
+                custom_tags: vec!["test".to_string()],
+                license: License::Cc0,
+                attribution: Some("by a tester".to_string()),
+                ..StoreSpriteArgs::default()
+            },
         );
 
         let store_tx = Transaction::new_signed_with_payer(
@@ -83,6 +108,962 @@ mod sprite_manager_test {
 
         let sprite_manager_account_data: SpriteAccount =
             try_from_slice_unchecked(&sprite_manager_account.data).expect("should deserialize");
-        println!("sprite_account: {:#?}", sprite_manager_account_data);
+        assert_eq!(sprite_manager_account_data.sprites.len(), 1);
+        let escrow_token = get_account(&mut context, &sprite_token_account).await;
+        let escrow_token = spl_token::state::Account::unpack(&escrow_token.data).unwrap();
+        assert_eq!((escrow_token.owner, escrow_token.amount), (escrow_addr, 1));
+
+        let stored = sprite_manager_account_data
+            .find_sprite(&sprite_metadata.mint.pubkey())
+            .expect("sprite should be stored");
+        assert_eq!(stored.artist, context.payer.pubkey());
+        assert_eq!(stored.license, License::Cc0);
+        assert_eq!(stored.created_at, sprite_manager_account_data.updated_at);
+        assert!(sprite_manager_account_data.created_at <= sprite_manager_account_data.updated_at);
+        assert!(sprite_manager_account_data.created_at.unix_timestamp > 0);
+    }
+
+    #[tokio::test]
+    async fn test_equip_and_unequip() {
+        let mut context = program_test().start_with_context().await;
+
+        let (metadata, _, _) = create_nft(&mut context, false, None).await;
+        let (sprite_manager_addr, _escrow_addr) =
+            create_sprite_account_helper(&mut context, &metadata).await;
+
+        let (body, _, _) = create_nft(&mut context, false, None).await;
+        let (hat, _, _) = create_nft(&mut context, false, None).await;
+        let (other_hat, _, _) = create_nft(&mut context, false, None).await;
+        for (sprite, slot, z_order) in [
+            (&hat, EquipmentSlot::Hat, 2),
+            (&body, EquipmentSlot::Body, 0),
+            (&other_hat, EquipmentSlot::Hat, 2),
+        ] {
+            store_sprite_helper(
+                &mut context,
+                &metadata,
+                sprite,
+                StoreSpriteArgs {
+                    name: "sprite".to_string(),
+                    description: "a sprite".to_string(),
+                    slot,
+                    z_order,
+                    ..StoreSpriteArgs::default()
+                },
+            )
+            .await
+            .expect("storing the sprite should succeed");
+        }
+
+        let owner = context.payer.pubkey();
+        let equip = |sprite: &Metadata| {
+            equip_sprite(
+                &sprite_manager::id(),
+                &sprite_manager_addr,
+                &metadata.mint.pubkey(),
+                &metadata.token.pubkey(),
+                &sprite.mint.pubkey(),
+                &owner,
+            )
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[equip(&hat), equip(&body)],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect("equipping should succeed");
+
+        let sprite_account = get_account(&mut context, &sprite_manager_addr).await;
+        let sprite_account: SpriteAccount =
+            try_from_slice_unchecked(&sprite_account.data).expect("should deserialize");
+        let loadout: Vec<_> = sprite_account
+            .loadout()
+            .iter()
+            .map(|sprite| sprite.mint)
+            .collect();
+        assert_eq!(loadout, vec![body.mint.pubkey(), hat.mint.pubkey()]);
+
+        // The hat slot is already taken.
+        let tx = Transaction::new_signed_with_payer(
+            &[equip(&other_hat)],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect_err("equipping into an occupied slot should fail");
+        assert_custom_error!(err, SpriteManagerError::SlotOccupied);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                unequip_sprite(
+                    &sprite_manager::id(),
+                    &sprite_manager_addr,
+                    &metadata.mint.pubkey(),
+                    &metadata.token.pubkey(),
+                    &hat.mint.pubkey(),
+                    &context.payer.pubkey(),
+                ),
+                equip(&other_hat),
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect("swapping hats should succeed");
+
+        let sprite_account = get_account(&mut context, &sprite_manager_addr).await;
+        let sprite_account: SpriteAccount =
+            try_from_slice_unchecked(&sprite_account.data).expect("should deserialize");
+        let loadout: Vec<_> = sprite_account
+            .loadout()
+            .iter()
+            .map(|sprite| sprite.mint)
+            .collect();
+        assert_eq!(loadout, vec![body.mint.pubkey(), other_hat.mint.pubkey()]);
+    }
+
+    #[tokio::test]
+    async fn test_palette_variants() {
+        let mut context = program_test().start_with_context().await;
+
+        let (metadata, _, _) = create_nft(&mut context, false, None).await;
+        let (sprite_manager_addr, _escrow_addr) =
+            create_sprite_account_helper(&mut context, &metadata).await;
+
+        let black = Rgba {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        let white = Rgba {
+            r: 255,
+            g: 255,
+            b: 255,
+            a: 255,
+        };
+        let palette = Palette {
+            colors: vec![black, white],
+            variants: vec![PaletteVariant {
+                name: "inverted".to_string(),
+                colors: vec![white, black],
+            }],
+        };
+        let args = |palette: Palette| StoreSpriteArgs {
+            name: "sprite".to_string(),
+            description: "a sprite".to_string(),
+            palette: Some(palette),
+            ..StoreSpriteArgs::default()
+        };
+
+        // A variant has to recolor every entry of the base palette.
+        let (bad_sprite, _, _) = create_nft(&mut context, false, None).await;
+        let mut bad_palette = palette.clone();
+        bad_palette.variants[0].colors.pop();
+        let err = store_sprite_helper(&mut context, &metadata, &bad_sprite, args(bad_palette))
+            .await
+            .expect_err("storing a mismatched palette should fail");
+        assert_custom_error!(err, SpriteManagerError::InvalidPalette);
+
+        let (sprite, _, _) = create_nft(&mut context, false, None).await;
+        store_sprite_helper(&mut context, &metadata, &sprite, args(palette))
+            .await
+            .expect("storing the sprite should succeed");
+
+        let owner = context.payer.pubkey();
+        let set_variant = |variant: Option<&str>| {
+            set_palette_variant(
+                &sprite_manager::id(),
+                &sprite_manager_addr,
+                &metadata.mint.pubkey(),
+                &metadata.token.pubkey(),
+                &sprite.mint.pubkey(),
+                &owner,
+                variant.map(str::to_string),
+            )
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_variant(Some("missing"))],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect_err("selecting an unknown variant should fail");
+        assert_custom_error!(err, SpriteManagerError::PaletteVariantNotFound);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_variant(Some("inverted"))],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect("selecting a variant should succeed");
+
+        let sprite_account = get_account(&mut context, &sprite_manager_addr).await;
+        let sprite_account: SpriteAccount =
+            try_from_slice_unchecked(&sprite_account.data).expect("should deserialize");
+        let stored = sprite_account
+            .find_sprite(&sprite.mint.pubkey())
+            .expect("sprite should be stored");
+        assert_eq!(stored.active_palette(), Some(&[white, black][..]));
+
+        let tx = Transaction::new_signed_with_payer(
+            &[set_variant(None)],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect("resetting the variant should succeed");
+
+        let sprite_account = get_account(&mut context, &sprite_manager_addr).await;
+        let sprite_account: SpriteAccount =
+            try_from_slice_unchecked(&sprite_account.data).expect("should deserialize");
+        let stored = sprite_account
+            .find_sprite(&sprite.mint.pubkey())
+            .expect("sprite should be stored");
+        assert_eq!(stored.active_palette(), Some(&[black, white][..]));
+    }
+
+    #[tokio::test]
+    async fn test_sponsored_store() {
+        let mut context = program_test().start_with_context().await;
+
+        let fixture = SpriteFixture::empty(&mut context).await;
+        let (sprite, _, _) = create_nft(&mut context, false, None).await;
+        let sponsor = Keypair::new();
+        airdrop(&mut context, &sponsor.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        // A relayer pays the transaction fee while the depositor funds the escrow's token account
+        // and the sprite account's growth.
+        let depositor = context.payer.pubkey();
+        let depositor_lamports = get_account(&mut context, &depositor).await.lamports;
+        let instruction = StoreSpriteBuilder::new(
+            fixture.base.mint.pubkey(),
+            sprite.mint.pubkey(),
+            depositor,
+            sprite_args(0),
+        )
+        .sprite_token_account(sprite.token.pubkey())
+        .instruction();
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&sponsor.pubkey()),
+            &[&sponsor, &context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect("a sponsored store should succeed");
+
+        let sprite_account = fixture.get_data(&mut context).await;
+        assert!(sprite_account.find_sprite(&sprite.mint.pubkey()).is_some());
+        assert!(get_account(&mut context, &depositor).await.lamports < depositor_lamports);
+    }
+
+    #[tokio::test]
+    async fn test_store_requires_verified_creator() {
+        let mut context = program_test().start_with_context().await;
+
+        let (metadata, _, _) = create_nft(&mut context, false, None).await;
+        create_sprite_account_helper(&mut context, &metadata).await;
+
+        let sprite = Metadata::new();
+        sprite
+            .create_v2(
+                &mut context,
+                "Sprite".to_string(),
+                "SPR".to_string(),
+                "uri".to_string(),
+                None,
+                0,
+                true,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let err = store_sprite_helper(
+            &mut context,
+            &metadata,
+            &sprite,
+            StoreSpriteArgs {
+                name: "sprite".to_string(),
+                description: "a sprite".to_string(),
+                license: License::CustomUri("https://example.com/license".to_string()),
+                ..StoreSpriteArgs::default()
+            },
+        )
+        .await
+        .expect_err("storing a sprite without a verified creator should fail");
+        assert_custom_error!(err, SpriteManagerError::NoVerifiedCreator);
+    }
+
+    #[tokio::test]
+    async fn test_attributes() {
+        let mut context = program_test().start_with_context().await;
+
+        let (metadata, _, _) = create_nft(&mut context, false, None).await;
+        let (sprite_manager_addr, _escrow_addr) =
+            create_sprite_account_helper(&mut context, &metadata).await;
+
+        let args = |attributes: Vec<(String, AttributeValue)>| StoreSpriteArgs {
+            name: "sprite".to_string(),
+            description: "a sprite".to_string(),
+            attributes,
+            ..StoreSpriteArgs::default()
+        };
+
+        let (duplicate, _, _) = create_nft(&mut context, false, None).await;
+        let err = store_sprite_helper(
+            &mut context,
+            &metadata,
+            &duplicate,
+            args(vec![
+                ("season".to_string(), 3.into()),
+                ("season".to_string(), 4.into()),
+            ]),
+        )
+        .await
+        .expect_err("storing duplicate attribute keys should fail");
+        assert_custom_error!(err, SpriteManagerError::DuplicateAttributeKey);
+
+        let (epic, _, _) = create_nft(&mut context, false, None).await;
+        let (common, _, _) = create_nft(&mut context, false, None).await;
+        for (sprite, rarity) in [(&epic, "epic"), (&common, "common")] {
+            store_sprite_helper(
+                &mut context,
+                &metadata,
+                sprite,
+                args(vec![
+                    ("rarity".to_string(), rarity.into()),
+                    ("season".to_string(), 3.into()),
+                    ("animated".to_string(), false.into()),
+                ]),
+            )
+            .await
+            .expect("storing the sprite should succeed");
+        }
+
+        let sprite_account = get_account(&mut context, &sprite_manager_addr).await;
+        let sprite_account: SpriteAccount =
+            try_from_slice_unchecked(&sprite_account.data).expect("should deserialize");
+        let epics: Vec<_> = sprite_account
+            .sprites_where("rarity", "epic")
+            .iter()
+            .map(|sprite| sprite.mint)
+            .collect();
+        assert_eq!(epics, vec![epic.mint.pubkey()]);
+        assert_eq!(sprite_account.sprites_where("season", 3).len(), 2);
+        assert!(sprite_account.sprites_where("animated", true).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sprite_families() {
+        let mut context = program_test().start_with_context().await;
+
+        let (metadata, _, _) = create_nft(&mut context, false, None).await;
+        let (sprite_manager_addr, _escrow_addr) =
+            create_sprite_account_helper(&mut context, &metadata).await;
+
+        let family = Pubkey::new_unique();
+        let (summer, _, _) = create_nft(&mut context, false, None).await;
+        let (winter, _, _) = create_nft(&mut context, false, None).await;
+        let (loner, _, _) = create_nft(&mut context, false, None).await;
+        for (sprite, family) in [
+            (&summer, Some(family)),
+            (&winter, Some(family)),
+            (&loner, None),
+        ] {
+            store_sprite_helper(
+                &mut context,
+                &metadata,
+                sprite,
+                StoreSpriteArgs {
+                    name: "sprite".to_string(),
+                    description: "a sprite".to_string(),
+                    slot: EquipmentSlot::Top,
+                    z_order: 1,
+                    family,
+                    ..StoreSpriteArgs::default()
+                },
+            )
+            .await
+            .expect("storing the sprite should succeed");
+        }
+
+        let sprite_account = get_account(&mut context, &sprite_manager_addr).await;
+        let sprite_account: SpriteAccount =
+            try_from_slice_unchecked(&sprite_account.data).expect("should deserialize");
+        let stored_family = sprite_account
+            .find_family(&family)
+            .expect("family should be recorded");
+        assert_eq!(stored_family.variant_count, 2);
+        assert_eq!(stored_family.active_variant, summer.mint.pubkey());
+        assert_eq!(sprite_account.variants(&family).len(), 2);
+
+        let owner = context.payer.pubkey();
+        let ix = |instruction: fn(
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+        ) -> Instruction,
+                  sprite: &Metadata| {
+            instruction(
+                &sprite_manager::id(),
+                &sprite_manager_addr,
+                &metadata.mint.pubkey(),
+                &metadata.token.pubkey(),
+                &sprite.mint.pubkey(),
+                &owner,
+            )
+        };
+
+        // Switching the variant of an equipped family swaps what is worn.
+        let tx = Transaction::new_signed_with_payer(
+            &[ix(equip_sprite, &summer), ix(set_active_variant, &winter)],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect("switching variants should succeed");
+
+        let sprite_account = get_account(&mut context, &sprite_manager_addr).await;
+        let sprite_account: SpriteAccount =
+            try_from_slice_unchecked(&sprite_account.data).expect("should deserialize");
+        assert_eq!(
+            sprite_account.find_family(&family).unwrap().active_variant,
+            winter.mint.pubkey()
+        );
+        let loadout: Vec<_> = sprite_account
+            .loadout()
+            .iter()
+            .map(|sprite| sprite.mint)
+            .collect();
+        assert_eq!(loadout, vec![winter.mint.pubkey()]);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix(set_active_variant, &loner)],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect_err("a sprite without a family has no variants");
+        assert_custom_error!(err, SpriteManagerError::SpriteHasNoFamily);
+    }
+
+    #[tokio::test]
+    async fn test_timestamps() {
+        let mut context = program_test().start_with_context().await;
+
+        let family = Pubkey::new_unique();
+        let palette = Palette {
+            colors: vec![Rgba {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 255,
+            }],
+            variants: vec![PaletteVariant {
+                name: "night".to_string(),
+                colors: vec![Rgba {
+                    r: 0,
+                    g: 0,
+                    b: 64,
+                    a: 255,
+                }],
+            }],
+        };
+        let mut slot = 100;
+        let mut fixture = SpriteFixture::empty(&mut context).await;
+        for index in 0..2 {
+            slot += 10;
+            context.warp_to_slot(slot).unwrap();
+            fixture
+                .store(
+                    &mut context,
+                    StoreSpriteArgs {
+                        palette: Some(palette.clone()),
+                        family: Some(family),
+                        ..sprite_args(index)
+                    },
+                )
+                .await
+                .expect("storing the sprite should succeed");
+        }
+        let first = fixture.sprites[0].mint.pubkey();
+        let second = fixture.sprites[1].mint.pubkey();
+
+        let mints = |sprites: Vec<&Sprite>| -> Vec<Pubkey> {
+            sprites.iter().map(|sprite| sprite.mint).collect()
+        };
+        let sprite_account = fixture.get_data(&mut context).await;
+        assert_eq!(
+            mints(sprite_account.sprites_by_created_at()),
+            vec![second, first]
+        );
+
+        let owner = context.payer.pubkey();
+        let base_mint = fixture.base.mint.pubkey();
+        let base_token_account = fixture.base.token.pubkey();
+        let steps = [
+            (
+                second,
+                SetActiveVariantBuilder::new(base_mint, second, owner)
+                    .base_token_account(base_token_account)
+                    .instruction(),
+            ),
+            (
+                second,
+                EquipSpriteBuilder::new(base_mint, second, owner)
+                    .base_token_account(base_token_account)
+                    .instruction(),
+            ),
+            (
+                second,
+                UnequipSpriteBuilder::new(base_mint, second, owner)
+                    .base_token_account(base_token_account)
+                    .instruction(),
+            ),
+            (
+                first,
+                SetPaletteVariantBuilder::new(base_mint, first, owner, Some("night".to_string()))
+                    .base_token_account(base_token_account)
+                    .instruction(),
+            ),
+        ];
+
+        // Every change moves both the account's and the changed sprite's `updated_at` forward.
+        for (mint, instruction) in steps {
+            let before = fixture.get_data(&mut context).await;
+            slot += 10;
+            context.warp_to_slot(slot).unwrap();
+            let blockhash = context
+                .banks_client
+                .get_new_latest_blockhash(&context.last_blockhash)
+                .await
+                .unwrap();
+            let tx = Transaction::new_signed_with_payer(
+                &[instruction],
+                Some(&owner),
+                &[&context.payer],
+                blockhash,
+            );
+            context
+                .banks_client
+                .process_transaction(tx)
+                .await
+                .expect("updating the sprite should succeed");
+
+            let after = fixture.get_data(&mut context).await;
+            let (sprite_before, sprite_after) = (
+                before.find_sprite(&mint).unwrap(),
+                after.find_sprite(&mint).unwrap(),
+            );
+            assert!(after.updated_at > before.updated_at);
+            assert!(sprite_after.updated_at > sprite_before.updated_at);
+            assert_eq!(sprite_after.updated_at, after.updated_at);
+            assert_eq!(sprite_after.created_at, sprite_before.created_at);
+            assert_eq!(after.created_at, before.created_at);
+        }
+
+        let sprite_account = fixture.get_data(&mut context).await;
+        assert_eq!(
+            mints(sprite_account.sprites_by_updated_at()),
+            vec![first, second]
+        );
+        assert_eq!(
+            mints(sprite_account.sprites_by_created_at()),
+            vec![second, first]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_renditions() {
+        let mut context = program_test().start_with_context().await;
+
+        let (metadata, _, _) = create_nft(&mut context, false, None).await;
+        let (sprite_manager_addr, _escrow_addr) =
+            create_sprite_account_helper(&mut context, &metadata).await;
+
+        let rendition = |scale: u8, format: RenditionFormat| Rendition {
+            uri: format!("https://example.com/sprite@{}x", scale),
+            scale,
+            width: 16 * scale as u32,
+            height: 16 * scale as u32,
+            format,
+            content_hash: [scale; 32],
+        };
+        let args = |renditions: Vec<Rendition>| StoreSpriteArgs {
+            name: "sprite".to_string(),
+            description: "a sprite".to_string(),
+            renditions,
+            ..StoreSpriteArgs::default()
+        };
+
+        let (bad_sprite, _, _) = create_nft(&mut context, false, None).await;
+        let err = store_sprite_helper(
+            &mut context,
+            &metadata,
+            &bad_sprite,
+            args(vec![rendition(0, RenditionFormat::Png)]),
+        )
+        .await
+        .expect_err("storing a zero-scale rendition should fail");
+        assert_custom_error!(err, SpriteManagerError::InvalidRendition);
+
+        let (sprite, _, _) = create_nft(&mut context, false, None).await;
+        store_sprite_helper(
+            &mut context,
+            &metadata,
+            &sprite,
+            args(vec![
+                rendition(1, RenditionFormat::Png),
+                rendition(2, RenditionFormat::WebP),
+                rendition(4, RenditionFormat::Png),
+                rendition(4, RenditionFormat::Ktx2),
+            ]),
+        )
+        .await
+        .expect("storing the sprite should succeed");
+
+        let sprite_account = get_account(&mut context, &sprite_manager_addr).await;
+        let sprite_account: SpriteAccount =
+            try_from_slice_unchecked(&sprite_account.data).expect("should deserialize");
+        let stored = sprite_account
+            .find_sprite(&sprite.mint.pubkey())
+            .expect("sprite should be stored");
+
+        let best = |scale, formats: &[RenditionFormat]| {
+            stored
+                .best_rendition(scale, formats)
+                .map(|rendition| (rendition.scale, rendition.format))
+        };
+        use RenditionFormat::*;
+        assert_eq!(best(1, &[Png]), Some((1, Png)));
+        assert_eq!(best(2, &[Png]), Some((4, Png)));
+        assert_eq!(best(2, &[Png, WebP]), Some((2, WebP)));
+        assert_eq!(best(4, &[Ktx2, Png]), Some((4, Ktx2)));
+        assert_eq!(best(8, &[Png]), Some((4, Png)));
+        assert_eq!(best(1, &[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_program_config() {
+        let mut context = program_test().start_with_context().await;
+
+        let admin = context.payer.pubkey();
+        set_upgrade_authority(&mut context, &admin);
+        let limits = ConfigLimits {
+            max_name_len: 8,
+            ..ConfigLimits::default()
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[initialize_config(
+                &sprite_manager::id(),
+                &admin,
+                limits,
+                FeeSchedule::default(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect("initializing the config should succeed");
+
+        let (metadata, _, _) = create_nft(&mut context, false, None).await;
+        create_sprite_account_helper(&mut context, &metadata).await;
+        let (sprite, _, _) = create_nft(&mut context, false, None).await;
+        let args = |name: &str| StoreSpriteArgs {
+            name: name.to_string(),
+            description: "a sprite".to_string(),
+            ..StoreSpriteArgs::default()
+        };
+
+        let err = store_sprite_helper(&mut context, &metadata, &sprite, args("too long a name"))
+            .await
+            .expect_err("storing a name over the limit should fail");
+        assert_custom_error!(err, SpriteManagerError::LimitExceeded);
+
+        let update = |admin: &Keypair, paused: bool, blockhash| {
+            Transaction::new_signed_with_payer(
+                &[update_config(
+                    &sprite_manager::id(),
+                    &admin.pubkey(),
+                    Some(paused),
+                    None,
+                    None,
+                )],
+                Some(&admin.pubkey()),
+                &[admin],
+                blockhash,
+            )
+        };
+        context
+            .banks_client
+            .process_transaction(update(&context.payer, true, context.last_blockhash))
+            .await
+            .expect("pausing should succeed");
+
+        let err = store_sprite_helper(&mut context, &metadata, &sprite, args("sprite"))
+            .await
+            .expect_err("storing while paused should fail");
+        assert_custom_error!(err, SpriteManagerError::ProgramPaused);
+
+        context
+            .banks_client
+            .process_transaction(update(&context.payer, false, context.last_blockhash))
+            .await
+            .expect("unpausing should succeed");
+        // The paused attempt used the same blockhash, so move on to a new one before retrying.
+        context.last_blockhash = context
+            .banks_client
+            .get_new_latest_blockhash(&context.last_blockhash)
+            .await
+            .unwrap();
+        store_sprite_helper(&mut context, &metadata, &sprite, args("sprite"))
+            .await
+            .expect("storing the sprite should succeed");
+
+        // Hand the admin role over in two steps.
+        let new_admin = Keypair::new();
+        let impostor = Keypair::new();
+        let tx = Transaction::new_signed_with_payer(
+            &[propose_admin(
+                &sprite_manager::id(),
+                &admin,
+                Some(new_admin.pubkey()),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect("proposing an admin should succeed");
+
+        let tx = Transaction::new_signed_with_payer(
+            &[accept_admin(&sprite_manager::id(), &impostor.pubkey())],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &impostor],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect_err("only the proposed admin can accept");
+        assert_custom_error!(err, SpriteManagerError::NotPendingAdmin);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[accept_admin(&sprite_manager::id(), &new_admin.pubkey())],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &new_admin],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect("accepting the admin role should succeed");
+
+        let config = get_account(&mut context, &find_config_address().0).await;
+        let config: ProgramConfig = try_from_slice_unchecked(&config.data).unwrap();
+        assert_eq!(config.admin, new_admin.pubkey());
+        assert_eq!(config.pending_admin, None);
+        assert_eq!(config.limits, limits);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_config(
+                &sprite_manager::id(),
+                &admin,
+                Some(true),
+                None,
+                None,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .expect_err("the previous admin can no longer update the config");
+        assert_custom_error!(err, SpriteManagerError::InvalidAdmin);
+    }
+
+    #[tokio::test]
+    async fn test_store_fees() {
+        let mut context = program_test().start_with_context().await;
+
+        let payer = context.payer.pubkey();
+        set_upgrade_authority(&mut context, &payer);
+        let treasury = Pubkey::new_unique();
+        let fee_mint = Keypair::new();
+        create_mint(&mut context, &fee_mint, &payer, None, 0)
+            .await
+            .unwrap();
+        let payer_fee_account =
+            spl_associated_token_account::get_associated_token_address(&payer, &fee_mint.pubkey());
+        let treasury_fee_account = spl_associated_token_account::get_associated_token_address(
+            &treasury,
+            &fee_mint.pubkey(),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer,
+                    &payer,
+                    &fee_mint.pubkey(),
+                ),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer,
+                    &treasury,
+                    &fee_mint.pubkey(),
+                ),
+            ],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+        mint_tokens(
+            &mut context,
+            &fee_mint.pubkey(),
+            &payer_fee_account,
+            10,
+            &payer,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let fees = FeeSchedule {
+            store_fee_lamports: 5_000_000,
+            token_fee: Some(TokenFee {
+                mint: fee_mint.pubkey(),
+                amount: 3,
+            }),
+            treasury,
+            exempt_authorities: vec![],
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[initialize_config(
+                &sprite_manager::id(),
+                &payer,
+                ConfigLimits::default(),
+                fees.clone(),
+            )],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let (metadata, _, _) = create_nft(&mut context, false, None).await;
+        create_sprite_account_helper(&mut context, &metadata).await;
+        let args = StoreSpriteArgs {
+            name: "sprite".to_string(),
+            description: "a sprite".to_string(),
+            ..StoreSpriteArgs::default()
+        };
+
+        let (sprite, _, _) = create_nft(&mut context, false, None).await;
+        store_sprite_helper(&mut context, &metadata, &sprite, args.clone())
+            .await
+            .expect("storing the sprite should succeed");
+
+        let token_balance = |account: Account| {
+            spl_token::state::Account::unpack(&account.data)
+                .unwrap()
+                .amount
+        };
+        assert_eq!(
+            get_account(&mut context, &treasury).await.lamports,
+            fees.store_fee_lamports
+        );
+        assert_eq!(
+            token_balance(get_account(&mut context, &treasury_fee_account).await),
+            3
+        );
+
+        // Exempt payers store for free.
+        let tx = Transaction::new_signed_with_payer(
+            &[update_config(
+                &sprite_manager::id(),
+                &payer,
+                None,
+                None,
+                Some(FeeSchedule {
+                    exempt_authorities: vec![payer],
+                    ..fees.clone()
+                }),
+            )],
+            Some(&payer),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let (free_sprite, _, _) = create_nft(&mut context, false, None).await;
+        store_sprite_helper(&mut context, &metadata, &free_sprite, args)
+            .await
+            .expect("storing the sprite should succeed");
+        assert_eq!(
+            get_account(&mut context, &treasury).await.lamports,
+            fees.store_fee_lamports
+        );
+        assert_eq!(
+            token_balance(get_account(&mut context, &treasury_fee_account).await),
+            3
+        );
     }
 }
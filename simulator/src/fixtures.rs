@@ -0,0 +1,142 @@
+use borsh::BorshSerialize;
+use mpl_token_metadata::{
+    pda::{find_master_edition_account, find_metadata_account},
+    state::{
+        Creator, Data, Key as MetadataKey, MasterEditionV2, Metadata, TokenMetadataAccount,
+        TokenStandard,
+    },
+};
+use solana_program::{
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+};
+use solana_sdk::account::Account;
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+use crate::Simulator;
+
+/// The accounts of a master edition NFT whose token sits in its owner's associated token account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Nft {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub metadata: Pubkey,
+    pub master_edition: Pubkey,
+}
+
+impl Simulator {
+    /// Writes an initialized mint with no supply.
+    pub fn create_mint(&mut self, mint_authority: &Pubkey, decimals: u8) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let state = Mint {
+            mint_authority: COption::Some(*mint_authority),
+            supply: 0,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        self.set_packed(mint, state, spl_token::id());
+        mint
+    }
+
+    /// Writes `owner`'s associated token account for `mint` holding `amount`, adding `amount` to
+    /// the mint's supply.
+    pub fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey, amount: u64) -> Pubkey {
+        let mut mint_state = self.unpack::<Mint>(mint).expect("mint exists");
+        mint_state.supply += amount;
+        self.set_packed(*mint, mint_state, spl_token::id());
+
+        let token_account = get_associated_token_address(owner, mint);
+        let state = TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        };
+        self.set_packed(token_account, state, spl_token::id());
+        token_account
+    }
+
+    /// Writes an NFT held by `owner` whose metadata lists `creator` as its verified creator, as
+    /// token metadata leaves it after minting a master edition.
+    pub fn create_nft(&mut self, owner: &Pubkey, creator: &Pubkey) -> Nft {
+        let mint = self.create_mint(creator, 0);
+        let (master_edition, _) = find_master_edition_account(&mint);
+        let (metadata, _) = find_metadata_account(&mint);
+        let token_account = self.create_token_account(owner, &mint, 1);
+
+        // Minting a master edition hands the mint authority to the edition.
+        let mut mint_state = self.unpack::<Mint>(&mint).expect("mint exists");
+        mint_state.mint_authority = COption::Some(master_edition);
+        self.set_packed(mint, mint_state, spl_token::id());
+
+        let metadata_state = Metadata {
+            key: MetadataKey::MetadataV1,
+            update_authority: *creator,
+            mint,
+            data: Data {
+                name: "Sprite".to_string(),
+                symbol: "SPRITE".to_string(),
+                uri: "https://example.com/sprite.json".to_string(),
+                seller_fee_basis_points: 0,
+                creators: Some(vec![Creator {
+                    address: *creator,
+                    verified: true,
+                    share: 100,
+                }]),
+            },
+            token_standard: Some(TokenStandard::NonFungible),
+            ..Metadata::default()
+        };
+        self.set_metadata_account(metadata, metadata_state);
+
+        let master_edition_state = MasterEditionV2 {
+            key: MetadataKey::MasterEditionV2,
+            supply: 0,
+            max_supply: Some(0),
+        };
+        self.set_metadata_account(master_edition, master_edition_state);
+
+        Nft {
+            mint,
+            token_account,
+            metadata,
+            master_edition,
+        }
+    }
+
+    fn unpack<T: Pack + IsInitialized>(&self, address: &Pubkey) -> Option<T> {
+        self.account(address)
+            .and_then(|account| T::unpack(&account.data).ok())
+    }
+
+    fn set_packed<T: Pack>(&mut self, address: Pubkey, state: T, owner: Pubkey) {
+        let mut data = vec![0; T::LEN];
+        T::pack(state, &mut data).unwrap();
+        self.set_rent_exempt(address, data, owner);
+    }
+
+    fn set_metadata_account<T: TokenMetadataAccount + BorshSerialize>(
+        &mut self,
+        address: Pubkey,
+        state: T,
+    ) {
+        let mut data = state.try_to_vec().unwrap();
+        T::pad_length(&mut data).unwrap();
+        self.set_rent_exempt(address, data, mpl_token_metadata::id());
+    }
+
+    fn set_rent_exempt(&mut self, address: Pubkey, data: Vec<u8>, owner: Pubkey) {
+        let account = Account {
+            lamports: self.rent().minimum_balance(data.len()),
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.set_account(address, account);
+    }
+}
@@ -0,0 +1,266 @@
+//! Runs the sprite manager processor natively against in-memory accounts.
+//!
+//! A [`Simulator`] holds the accounts of a small ledger and executes transactions against them
+//! by calling [`Processor::process_instruction`] directly, with no BPF build and no bank. Cross
+//! program invocations are routed to native stand-ins for the system program, SPL Token, the
+//! associated token account program and token metadata's escrow, see [`programs`], so the
+//! sprite flows run end to end in milliseconds.
+//!
+//! Accounts are handed to programs in the runtime's serialized layout, so reallocation works as
+//! it does on-chain, and the runtime's rules for which program may change which account are
+//! checked after every invocation. Signatures are not checked: an account signs when the
+//! transaction's account metas say it does.
+//!
+//! ```
+//! use solana_program::pubkey::Pubkey;
+//! use sprite_manager::builders::CreateSpriteAccountBuilder;
+//! use sprite_manager_simulator::Simulator;
+//!
+//! let mut simulator = Simulator::new();
+//! let owner = Pubkey::new_unique();
+//! simulator.airdrop(&owner, 1_000_000_000);
+//! let base = simulator.create_nft(&owner, &owner);
+//!
+//! simulator
+//!     .process_instruction(
+//!         CreateSpriteAccountBuilder::new(base.mint, owner).instruction(),
+//!         &owner,
+//!     )
+//!     .unwrap();
+//! assert!(simulator.sprite_account(&base.mint).is_some());
+//! ```
+//!
+//! The syscall stubs the simulator installs are process-wide, so it cannot share a test binary
+//! with a `ProgramTest` running programs natively.
+
+mod fixtures;
+pub mod programs;
+mod runtime;
+
+use std::collections::HashMap;
+
+use solana_program::{
+    bpf_loader,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::{Clock, Slot, DEFAULT_MS_PER_SLOT},
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program, sysvar,
+};
+use solana_sdk::{account::Account, transaction::TransactionError};
+use sprite_manager::{
+    events::{parse_events, SpriteEvent},
+    pda::{find_config_address, find_program_data_address, find_sprite_address},
+    processor::Processor,
+    state::{ProgramConfig, SolanaAccount, SpriteAccount},
+};
+
+pub use fixtures::Nft;
+pub use runtime::ProcessInstruction;
+
+use runtime::{execute, run_in_context, InvokeContext};
+
+/// An in-memory ledger running the sprite manager and the programs it calls natively.
+#[derive(Clone)]
+pub struct Simulator {
+    accounts: HashMap<Pubkey, Account>,
+    programs: HashMap<Pubkey, ProcessInstruction>,
+    clock: Clock,
+    rent: Rent,
+    logs: Vec<String>,
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulator {
+    pub fn new() -> Self {
+        let mut simulator = Self {
+            accounts: HashMap::new(),
+            programs: HashMap::new(),
+            clock: Clock {
+                slot: 1,
+                unix_timestamp: 1_660_000_000,
+                ..Clock::default()
+            },
+            rent: Rent::default(),
+            logs: vec![],
+        };
+
+        simulator.add_program(sprite_manager::id(), Processor::process_instruction);
+        simulator.add_program(system_program::id(), programs::process_system_instruction);
+        simulator.add_program(spl_token::id(), spl_token::processor::Processor::process);
+        simulator.add_program(
+            spl_associated_token_account::id(),
+            spl_associated_token_account::processor::process_instruction,
+        );
+        simulator.add_program(
+            mpl_token_metadata::id(),
+            programs::process_token_metadata_instruction,
+        );
+
+        let rent = Account::new_data(1, &simulator.rent, &sysvar::id()).unwrap();
+        simulator.set_account(sysvar::rent::id(), rent);
+
+        simulator
+    }
+
+    /// Runs `process` for instructions to `program_id`, replacing the program there if any.
+    pub fn add_program(&mut self, program_id: Pubkey, process: ProcessInstruction) {
+        self.programs.insert(program_id, process);
+        self.accounts.insert(
+            program_id,
+            Account {
+                lamports: 1,
+                owner: bpf_loader::id(),
+                executable: true,
+                ..Account::default()
+            },
+        );
+    }
+
+    /// Records `authority` as the sprite manager's upgrade authority, the only signer
+    /// `InitializeConfig` accepts.
+    pub fn set_upgrade_authority(&mut self, authority: &Pubkey) {
+        let state = UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(*authority),
+        };
+        let account = Account::new_data(1, &state, &bpf_loader_upgradeable::id()).unwrap();
+        self.set_account(find_program_data_address().0, account);
+    }
+
+    pub fn set_account(&mut self, address: Pubkey, account: Account) {
+        self.accounts.insert(address, account);
+    }
+
+    pub fn account(&self, address: &Pubkey) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        self.account(address)
+            .map(|account| account.lamports)
+            .unwrap_or_default()
+    }
+
+    /// Credits `lamports` to `address`, creating a system account if there is none.
+    pub fn airdrop(&mut self, address: &Pubkey, lamports: u64) {
+        self.accounts
+            .entry(*address)
+            .or_insert_with(|| Account::new(0, 0, &system_program::id()))
+            .lamports += lamports;
+    }
+
+    pub fn rent(&self) -> &Rent {
+        &self.rent
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    /// Moves the clock to `slot`, advancing the timestamp by the default slot duration.
+    pub fn warp_to_slot(&mut self, slot: Slot) {
+        let elapsed_ms = slot.saturating_sub(self.clock.slot) * DEFAULT_MS_PER_SLOT;
+        self.clock.unix_timestamp += (elapsed_ms / 1000) as i64;
+        self.clock.slot = slot;
+    }
+
+    pub fn process_instruction(
+        &mut self,
+        instruction: Instruction,
+        payer: &Pubkey,
+    ) -> Result<(), TransactionError> {
+        self.process_transaction(&[instruction], payer)
+    }
+
+    /// Executes the instructions in order as a transaction paid for by `payer`. Accounts get the
+    /// privileges of the transaction's message, so the payer and any account one instruction
+    /// writes are writable in all of them. No fees are charged.
+    ///
+    /// When an instruction fails none of the changes are kept and the error names the failed
+    /// instruction's index, as with a real transaction.
+    pub fn process_transaction(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+    ) -> Result<(), TransactionError> {
+        let message = Message::new(instructions, Some(payer));
+        let instructions = message
+            .instructions
+            .iter()
+            .map(|instruction| Instruction {
+                program_id: message.account_keys[instruction.program_id_index as usize],
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|index| {
+                        let index = *index as usize;
+                        AccountMeta {
+                            pubkey: message.account_keys[index],
+                            is_signer: message.is_signer(index),
+                            is_writable: message.is_writable(index),
+                        }
+                    })
+                    .collect(),
+                data: instruction.data.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        let context = InvokeContext::new(
+            self.programs.clone(),
+            self.accounts.clone(),
+            self.clock.clone(),
+            self.rent,
+        );
+        let (result, context) = run_in_context(context, || {
+            instructions
+                .iter()
+                .enumerate()
+                .try_for_each(|(index, instruction)| {
+                    execute(instruction)
+                        .map_err(|err| TransactionError::InstructionError(index as u8, err))
+                })
+        });
+
+        self.logs = context.logs;
+        if result.is_ok() {
+            self.accounts = context.accounts;
+        }
+        result
+    }
+
+    /// The log messages of the last transaction, in the runtime's format.
+    pub fn logs(&self) -> &[String] {
+        &self.logs
+    }
+
+    /// The events the sprite manager emitted in the last transaction.
+    pub fn events(&self) -> Vec<SpriteEvent> {
+        parse_events(&self.logs)
+    }
+
+    pub fn sprite_account(&self, base_mint: &Pubkey) -> Option<SpriteAccount> {
+        self.decode(&find_sprite_address(base_mint).0)
+    }
+
+    pub fn config(&self) -> Option<ProgramConfig> {
+        self.decode(&find_config_address().0)
+    }
+
+    fn decode<T: SolanaAccount>(&self, address: &Pubkey) -> Option<T> {
+        self.account(address)
+            .filter(|account| account.owner == sprite_manager::id() && !account.data.is_empty())
+            .and_then(|account| T::safe_deserialize(&account.data).ok())
+    }
+}
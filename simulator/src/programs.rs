@@ -0,0 +1,131 @@
+//! Native stand-ins for the programs the sprite manager calls.
+//!
+//! SPL Token and the associated token account program run their own processors. The system
+//! program and token metadata are mocked with just the instructions the sprite flows use.
+
+use borsh::BorshDeserialize;
+use mpl_token_metadata::{escrow::process_create_escrow_account, instruction::MetadataInstruction};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    system_instruction::{SystemError, SystemInstruction, MAX_PERMITTED_DATA_LENGTH},
+    system_program,
+};
+
+/// Mocks the system program's `CreateAccount`, `Allocate`, `Assign` and `Transfer`.
+pub fn process_system_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction: SystemInstruction =
+        limited_deserialize(instruction_data, instruction_data.len() as u64)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let account_info_iter = &mut accounts.iter();
+
+    match instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => {
+            let from_info = next_account_info(account_info_iter)?;
+            let to_info = next_account_info(account_info_iter)?;
+            if to_info.lamports() > 0 {
+                msg!("Create Account: account {} already in use", to_info.key);
+                return Err(custom(SystemError::AccountAlreadyInUse));
+            }
+            allocate(to_info, space)?;
+            assign(to_info, &owner)?;
+            transfer(from_info, to_info, lamports)
+        }
+        SystemInstruction::Allocate { space } => {
+            allocate(next_account_info(account_info_iter)?, space)
+        }
+        SystemInstruction::Assign { owner } => {
+            assign(next_account_info(account_info_iter)?, &owner)
+        }
+        SystemInstruction::Transfer { lamports } => {
+            let from_info = next_account_info(account_info_iter)?;
+            let to_info = next_account_info(account_info_iter)?;
+            transfer(from_info, to_info, lamports)
+        }
+        _ => {
+            msg!("Unsupported system instruction {:?}", instruction);
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+}
+
+fn custom(error: SystemError) -> ProgramError {
+    ProgramError::Custom(error as u32)
+}
+
+fn allocate(account_info: &AccountInfo, space: u64) -> ProgramResult {
+    if !account_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !account_info.data_is_empty() || *account_info.owner != system_program::id() {
+        msg!("Allocate: account {} already in use", account_info.key);
+        return Err(custom(SystemError::AccountAlreadyInUse));
+    }
+    if space > MAX_PERMITTED_DATA_LENGTH {
+        return Err(custom(SystemError::InvalidAccountDataLength));
+    }
+    account_info.realloc(space as usize, true)
+}
+
+fn assign(account_info: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account_info.owner == owner {
+        return Ok(());
+    }
+    if !account_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    account_info.assign(owner);
+    Ok(())
+}
+
+fn transfer(from_info: &AccountInfo, to_info: &AccountInfo, lamports: u64) -> ProgramResult {
+    if !from_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !from_info.data_is_empty() || *from_info.owner != system_program::id() {
+        msg!("Transfer: `from` must not carry data");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let from_lamports = from_info
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or_else(|| custom(SystemError::ResultWithNegativeLamports))?;
+    **from_info.try_borrow_mut_lamports()? = from_lamports;
+    let to_lamports = to_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    **to_info.try_borrow_mut_lamports()? = to_lamports;
+    Ok(())
+}
+
+/// Mocks token metadata with only `CreateEscrowAccount`, which runs the real escrow processor.
+pub fn process_token_metadata_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match MetadataInstruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?
+    {
+        MetadataInstruction::CreateEscrowAccount => {
+            process_create_escrow_account(program_id, accounts)
+        }
+        _ => {
+            msg!("Unsupported token metadata instruction");
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+}
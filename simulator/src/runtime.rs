@@ -0,0 +1,487 @@
+use std::{cell::RefCell, collections::HashMap, sync::Once};
+
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{
+        deserialize, ProgramResult, BPF_ALIGN_OF_U128, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER,
+        SUCCESS,
+    },
+    instruction::{Instruction, InstructionError},
+    program_error::{ProgramError, UNSUPPORTED_SYSVAR},
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+};
+use solana_sdk::account::Account;
+
+/// A program's entrypoint, called natively in place of its BPF build.
+pub type ProcessInstruction = fn(&Pubkey, &[AccountInfo], &[u8]) -> ProgramResult;
+
+/// State of the transaction running on this thread, read by the syscall stubs.
+pub(crate) struct InvokeContext {
+    pub programs: HashMap<Pubkey, ProcessInstruction>,
+    pub accounts: HashMap<Pubkey, Account>,
+    pub clock: Clock,
+    pub rent: Rent,
+    pub logs: Vec<String>,
+    frames: Vec<Frame>,
+    /// The error of a failed cross-program invocation, which fails the transaction even when
+    /// the caller goes on.
+    error: Option<InstructionError>,
+}
+
+impl InvokeContext {
+    pub fn new(
+        programs: HashMap<Pubkey, ProcessInstruction>,
+        accounts: HashMap<Pubkey, Account>,
+        clock: Clock,
+        rent: Rent,
+    ) -> Self {
+        Self {
+            programs,
+            accounts,
+            clock,
+            rent,
+            logs: vec![],
+            frames: vec![],
+            error: None,
+        }
+    }
+}
+
+/// An invocation in progress and the accounts it was given as of its start or its last
+/// cross-program invocation.
+struct Frame {
+    program_id: Pubkey,
+    pre: Vec<(Pubkey, Snapshot)>,
+}
+
+#[derive(Clone)]
+struct Snapshot {
+    lamports: u64,
+    data: Vec<u8>,
+    owner: Pubkey,
+    executable: bool,
+    is_writable: bool,
+}
+
+impl Snapshot {
+    fn of(info: &AccountInfo) -> Result<Self, InstructionError> {
+        Ok(Self {
+            lamports: **info
+                .try_borrow_lamports()
+                .map_err(|_| InstructionError::AccountBorrowFailed)?,
+            data: info
+                .try_borrow_data()
+                .map_err(|_| InstructionError::AccountBorrowFailed)?
+                .to_vec(),
+            owner: *info.owner,
+            executable: info.executable,
+            is_writable: info.is_writable,
+        })
+    }
+
+    /// Applies the runtime's rules for the changes `program_id` may make to an account.
+    fn verify(&self, program_id: &Pubkey, post: &Snapshot) -> Result<(), InstructionError> {
+        let is_owner = self.owner == *program_id;
+        if self.owner != post.owner
+            && (!self.is_writable || !is_owner || post.data.iter().any(|byte| *byte != 0))
+        {
+            return Err(InstructionError::ModifiedProgramId);
+        }
+        if post.lamports < self.lamports && !is_owner {
+            return Err(InstructionError::ExternalAccountLamportSpend);
+        }
+        if post.lamports != self.lamports && !self.is_writable {
+            return Err(InstructionError::ReadonlyLamportChange);
+        }
+        if post.data.len() != self.data.len() && (!self.is_writable || !is_owner) {
+            return Err(InstructionError::AccountDataSizeChanged);
+        }
+        if post.data != self.data {
+            if !self.is_writable {
+                return Err(InstructionError::ReadonlyDataModified);
+            }
+            if !is_owner {
+                return Err(InstructionError::ExternalAccountDataModified);
+            }
+        }
+        if post.executable != self.executable {
+            return Err(InstructionError::ExecutableModified);
+        }
+
+        Ok(())
+    }
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Option<InvokeContext>> = const { RefCell::new(None) };
+}
+
+fn with_context<T>(f: impl FnOnce(&mut InvokeContext) -> T) -> Option<T> {
+    CONTEXT.with(|context| context.borrow_mut().as_mut().map(f))
+}
+
+/// Runs `f` with `context` installed for the syscall stubs and returns the context afterwards.
+pub(crate) fn run_in_context<T>(
+    context: InvokeContext,
+    f: impl FnOnce() -> T,
+) -> (T, InvokeContext) {
+    static INSTALL_STUBS: Once = Once::new();
+    INSTALL_STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(Stubs));
+    });
+
+    CONTEXT.with(|cell| *cell.borrow_mut() = Some(context));
+    let result = f();
+    let context = CONTEXT
+        .with(|cell| cell.borrow_mut().take())
+        .expect("invoke context is installed");
+    (result, context)
+}
+
+/// An account as handed to a program, with the privileges the instruction grants it.
+struct AccountInput {
+    key: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    account: Account,
+}
+
+/// Serializes accounts and instruction data the way the runtime hands them to on-chain programs,
+/// so `AccountInfo::realloc` works as it does on-chain. The result is kept in `u64`s for the
+/// alignment `entrypoint::deserialize` expects.
+fn serialize(inputs: &[AccountInput], data: &[u8], program_id: &Pubkey) -> Vec<u64> {
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&(inputs.len() as u64).to_le_bytes());
+    for (i, input) in inputs.iter().enumerate() {
+        if let Some(first) = inputs[..i].iter().position(|other| other.key == input.key) {
+            bytes.push(first as u8);
+            bytes.extend_from_slice(&[0; 7]);
+            continue;
+        }
+
+        bytes.push(NON_DUP_MARKER);
+        bytes.push(input.is_signer as u8);
+        bytes.push(input.is_writable as u8);
+        bytes.push(input.account.executable as u8);
+        // Filled in with the original data length by `deserialize`.
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(input.key.as_ref());
+        bytes.extend_from_slice(input.account.owner.as_ref());
+        bytes.extend_from_slice(&input.account.lamports.to_le_bytes());
+        bytes.extend_from_slice(&(input.account.data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&input.account.data);
+        bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        bytes.resize(
+            bytes.len() + (BPF_ALIGN_OF_U128 - bytes.len() % BPF_ALIGN_OF_U128) % BPF_ALIGN_OF_U128,
+            0,
+        );
+        bytes.extend_from_slice(&input.account.rent_epoch.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(data);
+    bytes.extend_from_slice(program_id.as_ref());
+
+    let mut buffer = vec![0u64; bytes.len().div_ceil(8)];
+    for (word, chunk) in buffer.iter_mut().zip(bytes.chunks(8)) {
+        let mut le = [0; 8];
+        le[..chunk.len()].copy_from_slice(chunk);
+        *word = u64::from_le_bytes(le);
+    }
+    buffer
+}
+
+/// The first info of every distinct account.
+fn unique<'a, 'b>(infos: &'a [AccountInfo<'b>]) -> impl Iterator<Item = &'a AccountInfo<'b>> {
+    infos
+        .iter()
+        .enumerate()
+        .filter(|(i, info)| !infos[..*i].iter().any(|other| other.key == info.key))
+        .map(|(_, info)| info)
+}
+
+/// Runs a top-level instruction, whose metas carry the transaction's privileges, against the
+/// context's accounts and stores the accounts it changed.
+pub(crate) fn execute(instruction: &Instruction) -> Result<(), InstructionError> {
+    let (process, inputs) = with_context(|context| {
+        let process = *context
+            .programs
+            .get(&instruction.program_id)
+            .ok_or(InstructionError::UnsupportedProgramId)?;
+        let inputs = instruction
+            .accounts
+            .iter()
+            .map(|meta| AccountInput {
+                key: meta.pubkey,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+                account: context
+                    .accounts
+                    .get(&meta.pubkey)
+                    .cloned()
+                    .unwrap_or_default(),
+            })
+            .collect::<Vec<_>>();
+        Ok::<_, InstructionError>((process, inputs))
+    })
+    .expect("invoke context is installed")?;
+
+    let mut buffer = serialize(&inputs, &instruction.data, &instruction.program_id);
+    // Safety: `buffer` is laid out as `deserialize` expects and outlives the account infos.
+    let (program_id, infos, data) = unsafe { deserialize(buffer.as_mut_ptr() as *mut u8) };
+    invoke_frame(process, program_id, &infos, data)?;
+
+    let accounts = unique(&infos)
+        .map(|info| {
+            let account = Account {
+                lamports: info.lamports(),
+                data: info.data.borrow().to_vec(),
+                owner: *info.owner,
+                executable: info.executable,
+                rent_epoch: info.rent_epoch,
+            };
+            (*info.key, account)
+        })
+        .collect::<Vec<_>>();
+    drop(infos);
+    with_context(|context| context.accounts.extend(accounts));
+
+    Ok(())
+}
+
+/// Calls `process` in a new frame, checks the changes it made and logs the invocation like the
+/// runtime does.
+fn invoke_frame(
+    process: ProcessInstruction,
+    program_id: &Pubkey,
+    infos: &[AccountInfo],
+    data: &[u8],
+) -> Result<(), InstructionError> {
+    let pre = unique(infos)
+        .map(|info| Ok((*info.key, Snapshot::of(info)?)))
+        .collect::<Result<Vec<_>, InstructionError>>()?;
+    with_context(|context| {
+        context.frames.push(Frame {
+            program_id: *program_id,
+            pre,
+        });
+        context.logs.push(format!(
+            "Program {} invoke [{}]",
+            program_id,
+            context.frames.len()
+        ));
+    });
+
+    let result = process(program_id, infos, data)
+        .map_err(|err| InstructionError::from(u64::from(err)))
+        .and_then(|()| verify_frame(infos));
+
+    with_context(|context| {
+        context.frames.pop();
+        let result = match context.error.take() {
+            Some(err) => Err(err),
+            None => result,
+        };
+        context.logs.push(match &result {
+            Ok(()) => format!("Program {} success", program_id),
+            Err(err) => format!("Program {} failed: {}", program_id, err),
+        });
+        result
+    })
+    .expect("invoke context is installed")
+}
+
+/// Checks the accounts of the innermost frame against the state they were handed over in.
+fn verify_frame(infos: &[AccountInfo]) -> Result<(), InstructionError> {
+    let (program_id, pre) = with_context(|context| {
+        let frame = context.frames.last().expect("a frame is running");
+        (frame.program_id, frame.pre.clone())
+    })
+    .expect("invoke context is installed");
+
+    let (mut pre_lamports, mut post_lamports) = (0u128, 0u128);
+    for info in unique(infos) {
+        let post = Snapshot::of(info)?;
+        if let Some((_, pre)) = pre.iter().find(|(key, _)| key == info.key) {
+            pre.verify(&program_id, &post)?;
+            pre_lamports += pre.lamports as u128;
+            post_lamports += post.lamports as u128;
+        }
+    }
+    if pre_lamports != post_lamports {
+        return Err(InstructionError::UnbalancedInstruction);
+    }
+
+    Ok(())
+}
+
+/// Takes the current state of the accounts the caller shares with a callee as the caller's new
+/// starting point. Before the call the caller's own changes are checked first, after it the
+/// changes are the callee's, which were checked in its frame.
+fn sync_caller(account_infos: &[AccountInfo], verify: bool) -> Result<(), InstructionError> {
+    let snapshots = unique(account_infos)
+        .map(|info| Ok((*info.key, Snapshot::of(info)?)))
+        .collect::<Result<Vec<_>, InstructionError>>()?;
+
+    with_context(|context| {
+        let frame = context.frames.last_mut().expect("a frame is running");
+        for (key, post) in snapshots {
+            if let Some((_, pre)) = frame.pre.iter_mut().find(|(other, _)| *other == key) {
+                if verify {
+                    pre.verify(&frame.program_id, &post)?;
+                }
+                *pre = Snapshot {
+                    is_writable: pre.is_writable,
+                    ..post
+                };
+            }
+        }
+        Ok(())
+    })
+    .expect("invoke context is installed")
+}
+
+fn invoke_signed(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), InstructionError> {
+    let caller = with_context(|context| context.frames.last().map(|frame| frame.program_id))
+        .flatten()
+        .ok_or(InstructionError::UnsupportedProgramId)?;
+    let signers = signers_seeds
+        .iter()
+        .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| InstructionError::InvalidSeeds)?;
+
+    // Accounts the instruction names but the caller did not pass are loaded read-only, like the
+    // sysvars some programs list without reading.
+    let mut missing = vec![];
+    for meta in &instruction.accounts {
+        match account_infos.iter().find(|info| *info.key == meta.pubkey) {
+            Some(info) => {
+                if (meta.is_signer && !info.is_signer && !signers.contains(info.key))
+                    || (meta.is_writable && !info.is_writable)
+                {
+                    return Err(InstructionError::PrivilegeEscalation);
+                }
+            }
+            None if meta.is_signer || meta.is_writable => {
+                return Err(InstructionError::MissingAccount)
+            }
+            None => missing.push(meta.pubkey),
+        }
+    }
+    let (process, missing) = with_context(|context| {
+        let process = context.programs.get(&instruction.program_id).copied();
+        let missing = missing
+            .iter()
+            .map(|key| AccountInput {
+                key: *key,
+                is_signer: false,
+                is_writable: false,
+                account: context.accounts.get(key).cloned().unwrap_or_default(),
+            })
+            .collect::<Vec<_>>();
+        (process, missing)
+    })
+    .expect("invoke context is installed");
+    let process = process.ok_or(InstructionError::UnsupportedProgramId)?;
+
+    sync_caller(account_infos, true)?;
+
+    let mut buffer = serialize(&missing, &[], &instruction.program_id);
+    // Safety: `buffer` is laid out as `deserialize` expects and outlives the account infos.
+    let (_, missing_infos, _) = unsafe { deserialize(buffer.as_mut_ptr() as *mut u8) };
+    let infos = instruction
+        .accounts
+        .iter()
+        .map(|meta| {
+            let info = account_infos
+                .iter()
+                .chain(missing_infos.iter())
+                .find(|info| *info.key == meta.pubkey)
+                .expect("every account was found or loaded");
+            // Like the runtime, an account listed more than once gets the union of its privileges.
+            let metas = instruction
+                .accounts
+                .iter()
+                .filter(|other| other.pubkey == meta.pubkey);
+            AccountInfo {
+                is_signer: metas.clone().any(|other| other.is_signer),
+                is_writable: metas.clone().any(|other| other.is_writable),
+                ..info.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    invoke_frame(process, &instruction.program_id, &infos, &instruction.data)?;
+    drop(infos);
+
+    sync_caller(account_infos, false)
+}
+
+/// Routes the syscalls of natively running programs to the transaction on the calling thread.
+struct Stubs;
+
+impl Stubs {
+    fn log(message: String) {
+        if with_context(|context| context.logs.push(message.clone())).is_none() {
+            println!("{}", message);
+        }
+    }
+
+    /// # Safety
+    /// `var_addr` must point to a `T`.
+    unsafe fn get_sysvar<T>(var_addr: *mut u8, get: impl FnOnce(&InvokeContext) -> T) -> u64 {
+        match with_context(|context| get(context)) {
+            Some(sysvar) => {
+                std::ptr::write(var_addr as *mut T, sysvar);
+                SUCCESS
+            }
+            None => UNSUPPORTED_SYSVAR,
+        }
+    }
+}
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        Self::log(format!("Program log: {}", message));
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields = fields.iter().map(base64::encode).collect::<Vec<_>>();
+        Self::log(format!("Program data: {}", fields.join(" ")));
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        invoke_signed(instruction, account_infos, signers_seeds).map_err(|err| {
+            let program_error =
+                ProgramError::try_from(err.clone()).unwrap_or(ProgramError::InvalidArgument);
+            with_context(|context| {
+                context.error.get_or_insert(err);
+            });
+            program_error
+        })
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { Self::get_sysvar(var_addr, |context| context.clock.clone()) }
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { Self::get_sysvar(var_addr, |context| context.rent) }
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        with_context(|context| context.frames.len() as u64).unwrap_or_default()
+    }
+}
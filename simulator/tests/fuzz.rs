@@ -0,0 +1,323 @@
+//! Property tests feeding malformed instruction data and account lists to the processor.
+//!
+//! Inputs start from valid instructions against a ledger with a sprite account, a stored sprite
+//! and a program config, and are then truncated, corrupted or rearranged. Whatever the input,
+//! the processor must return an error instead of panicking.
+
+use borsh::BorshDeserialize;
+use proptest::{prelude::*, sample::Index, test_runner::TestRunner};
+use solana_program::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+use solana_sdk::transaction::TransactionError;
+use sprite_manager::{
+    builders::{
+        CreateSpriteAccountBuilder, EquipSpriteBuilder, SetActiveVariantBuilder,
+        SetPaletteVariantBuilder, StoreSpriteBuilder, UnequipSpriteBuilder,
+    },
+    decoder::decode_instruction,
+    error::SpriteManagerError,
+    events::parse_events,
+    instruction::{
+        accept_admin, initialize_config, migrate_sprite_account, propose_admin, update_config,
+        SpriteManagerInstruction, StoreSpriteArgs,
+    },
+    pda::{find_config_address, find_escrow_address, find_sprite_address},
+    state::{
+        ConfigLimits, EquipmentSlot, FeeSchedule, ProgramConfig, SolanaAccount, SpriteAccount,
+        TokenFee,
+    },
+};
+use sprite_manager_simulator::{Nft, Simulator};
+
+const SOL: u64 = 1_000_000_000;
+
+/// A ledger on which every sprite manager instruction has a valid form.
+struct Ledger {
+    simulator: Simulator,
+    owner: Pubkey,
+    fees: FeeSchedule,
+    base: Nft,
+    spare: Nft,
+    /// Valid instructions the fuzzed inputs are derived from.
+    templates: Vec<Instruction>,
+    /// Accounts fuzzed account lists draw from.
+    pool: Vec<Pubkey>,
+}
+
+impl Ledger {
+    fn new() -> Self {
+        let mut simulator = Simulator::new();
+        let owner = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        simulator.airdrop(&owner, 10 * SOL);
+        simulator.set_upgrade_authority(&owner);
+
+        let fee_mint = simulator.create_mint(&owner, 0);
+        simulator.create_token_account(&owner, &fee_mint, 100);
+        simulator.create_token_account(&treasury, &fee_mint, 0);
+        let fees = FeeSchedule {
+            store_fee_lamports: 5_000,
+            token_fee: Some(TokenFee {
+                mint: fee_mint,
+                amount: 1,
+            }),
+            treasury,
+            exempt_authorities: vec![],
+        };
+        simulator
+            .process_instruction(
+                initialize_config(
+                    &sprite_manager::id(),
+                    &owner,
+                    ConfigLimits::default(),
+                    fees.clone(),
+                ),
+                &owner,
+            )
+            .unwrap();
+
+        let base = simulator.create_nft(&owner, &owner);
+        let hat = simulator.create_nft(&owner, &owner);
+        let spare = simulator.create_nft(&owner, &owner);
+        let other_base = simulator.create_nft(&owner, &owner);
+        simulator
+            .process_instruction(
+                CreateSpriteAccountBuilder::new(base.mint, owner).instruction(),
+                &owner,
+            )
+            .unwrap();
+        simulator
+            .process_instruction(
+                StoreSpriteBuilder::new(base.mint, hat.mint, owner, args())
+                    .fees(&fees)
+                    .instruction(),
+                &owner,
+            )
+            .unwrap();
+
+        let templates = vec![
+            CreateSpriteAccountBuilder::new(other_base.mint, owner).instruction(),
+            StoreSpriteBuilder::new(base.mint, spare.mint, owner, args())
+                .fees(&fees)
+                .instruction(),
+            EquipSpriteBuilder::new(base.mint, hat.mint, owner).instruction(),
+            UnequipSpriteBuilder::new(base.mint, hat.mint, owner).instruction(),
+            SetPaletteVariantBuilder::new(base.mint, hat.mint, owner, None).instruction(),
+            SetActiveVariantBuilder::new(base.mint, hat.mint, owner).instruction(),
+            initialize_config(
+                &sprite_manager::id(),
+                &owner,
+                ConfigLimits::default(),
+                fees.clone(),
+            ),
+            update_config(&sprite_manager::id(), &owner, Some(true), None, None),
+            propose_admin(&sprite_manager::id(), &owner, Some(treasury)),
+            accept_admin(&sprite_manager::id(), &treasury),
+            migrate_sprite_account(
+                &sprite_manager::id(),
+                &find_sprite_address(&base.mint).0,
+                &base.mint,
+                &owner,
+            ),
+        ];
+
+        let mut pool: Vec<Pubkey> = templates
+            .iter()
+            .flat_map(|instruction| instruction.accounts.iter().map(|meta| meta.pubkey))
+            .chain([
+                find_config_address().0,
+                find_sprite_address(&base.mint).0,
+                find_escrow_address(&base.mint).0,
+                base.token_account,
+                base.metadata,
+                hat.token_account,
+                sysvar::rent::id(),
+                system_program::id(),
+                sprite_manager::id(),
+                Pubkey::new_unique(),
+            ])
+            .collect();
+        pool.sort();
+        pool.dedup();
+
+        Self {
+            simulator,
+            owner,
+            fees,
+            base,
+            spare,
+            templates,
+            pool,
+        }
+    }
+
+    fn inputs(&self) -> impl Strategy<Value = (Vec<u8>, Vec<AccountMeta>)> {
+        let templates = self.templates.clone();
+        let pool = self.pool.clone();
+        (
+            any::<Index>(),
+            data_edit(),
+            prop::collection::vec(account_edit(), 0..4),
+        )
+            .prop_map(move |(template, data_edit, account_edits)| {
+                let template = template.get(&templates);
+                let data = data_edit.apply(&template.data);
+                let mut accounts = template.accounts.clone();
+                for edit in account_edits {
+                    edit.apply(&mut accounts, &pool);
+                }
+                (data, accounts)
+            })
+    }
+}
+
+fn args() -> StoreSpriteArgs {
+    StoreSpriteArgs {
+        name: "Straw hat".to_string(),
+        description: "A wide-brimmed hat".to_string(),
+        slot: EquipmentSlot::Hat,
+        z_order: 3,
+        ..StoreSpriteArgs::default()
+    }
+}
+
+#[derive(Clone, Debug)]
+enum DataEdit {
+    Keep,
+    Truncate(Index),
+    Overwrite(Index, u8),
+    Replace(Vec<u8>),
+}
+
+impl DataEdit {
+    fn apply(&self, data: &[u8]) -> Vec<u8> {
+        let mut data = data.to_vec();
+        match self {
+            DataEdit::Keep => {}
+            DataEdit::Truncate(len) => data.truncate(len.index(data.len() + 1)),
+            DataEdit::Overwrite(position, byte) => {
+                let position = position.index(data.len());
+                data[position] = *byte;
+            }
+            DataEdit::Replace(bytes) => data = bytes.clone(),
+        }
+        data
+    }
+}
+
+fn data_edit() -> impl Strategy<Value = DataEdit> {
+    prop_oneof![
+        Just(DataEdit::Keep),
+        any::<Index>().prop_map(DataEdit::Truncate),
+        (any::<Index>(), any::<u8>())
+            .prop_map(|(position, byte)| DataEdit::Overwrite(position, byte)),
+        prop::collection::vec(any::<u8>(), 0..64).prop_map(DataEdit::Replace),
+    ]
+}
+
+#[derive(Clone, Debug)]
+enum AccountEdit {
+    /// Puts another account from the pool at a position, with the given privileges.
+    Substitute(Index, Index, bool, bool),
+    Remove(Index),
+    Swap(Index, Index),
+}
+
+impl AccountEdit {
+    fn apply(&self, accounts: &mut Vec<AccountMeta>, pool: &[Pubkey]) {
+        let len = accounts.len();
+        if len == 0 {
+            return;
+        }
+        match self {
+            AccountEdit::Substitute(position, pubkey, is_signer, is_writable) => {
+                accounts[position.index(len)] = AccountMeta {
+                    pubkey: *pubkey.get(pool),
+                    is_signer: *is_signer,
+                    is_writable: *is_writable,
+                };
+            }
+            AccountEdit::Remove(position) => {
+                accounts.remove(position.index(len));
+            }
+            AccountEdit::Swap(a, b) => {
+                accounts.swap(a.index(len), b.index(len));
+            }
+        }
+    }
+}
+
+fn account_edit() -> impl Strategy<Value = AccountEdit> {
+    prop_oneof![
+        (any::<Index>(), any::<Index>(), any::<bool>(), any::<bool>()).prop_map(
+            |(position, pubkey, is_signer, is_writable)| AccountEdit::Substitute(
+                position,
+                pubkey,
+                is_signer,
+                is_writable
+            )
+        ),
+        any::<Index>().prop_map(AccountEdit::Remove),
+        (any::<Index>(), any::<Index>()).prop_map(|(a, b)| AccountEdit::Swap(a, b)),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_decoding_never_panics(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        let _ = SpriteManagerInstruction::try_from_slice(&data);
+        let _ = decode_instruction(&data, &[Pubkey::new_unique(); 4]);
+        let _ = SpriteAccount::safe_deserialize(&data);
+        let _ = ProgramConfig::safe_deserialize(&data);
+        let _ = parse_events(&[format!("Program data: {}", base64::encode(&data))]);
+    }
+}
+
+#[test]
+fn test_empty_account_data_is_rejected() {
+    assert!(SpriteAccount::safe_deserialize(&[]).is_err());
+    assert!(ProgramConfig::safe_deserialize(&[]).is_err());
+}
+
+#[test]
+fn test_processor_never_panics() {
+    let ledger = Ledger::new();
+
+    TestRunner::default()
+        .run(&ledger.inputs(), |(data, accounts)| {
+            let mut simulator = ledger.simulator.clone();
+            let instruction = Instruction {
+                program_id: sprite_manager::id(),
+                accounts,
+                data,
+            };
+            // Any outcome but a panic is fine.
+            let _ = simulator.process_instruction(instruction, &ledger.owner);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_store_from_wrong_token_account_is_rejected() {
+    let ledger = Ledger::new();
+    let mut simulator = ledger.simulator.clone();
+
+    // The owner's base NFT token account holds a token, but not the sprite's.
+    let instruction =
+        StoreSpriteBuilder::new(ledger.base.mint, ledger.spare.mint, ledger.owner, args())
+            .sprite_token_account(ledger.base.token_account)
+            .fees(&ledger.fees)
+            .instruction();
+
+    assert_eq!(
+        simulator.process_instruction(instruction, &ledger.owner),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(SpriteManagerError::InvalidTokenAccount as u32)
+        ))
+    );
+}
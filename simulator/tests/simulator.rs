@@ -0,0 +1,377 @@
+use num_traits::FromPrimitive;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program::invoke,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solana_sdk::transaction::TransactionError;
+use spl_associated_token_account::get_associated_token_address;
+use sprite_manager::{
+    builders::{CreateSpriteAccountBuilder, EquipSpriteBuilder, StoreSpriteBuilder},
+    error::SpriteManagerError,
+    events::SpriteEvent,
+    instruction::{initialize_config, StoreSpriteArgs},
+    pda::{find_escrow_address, find_sprite_address},
+    state::{ConfigLimits, EquipmentSlot, FeeSchedule, PerspectiveTags, StyleTags},
+};
+use sprite_manager_simulator::{Nft, Simulator};
+
+const SOL: u64 = 1_000_000_000;
+
+/// An owner with a base NFT and a sprite account for it.
+struct Setup {
+    simulator: Simulator,
+    owner: Pubkey,
+    base: Nft,
+}
+
+impl Setup {
+    fn new() -> Self {
+        let mut simulator = Simulator::new();
+        let owner = Pubkey::new_unique();
+        simulator.airdrop(&owner, 10 * SOL);
+        let base = simulator.create_nft(&owner, &owner);
+        simulator
+            .process_instruction(
+                CreateSpriteAccountBuilder::new(base.mint, owner).instruction(),
+                &owner,
+            )
+            .unwrap();
+
+        Self {
+            simulator,
+            owner,
+            base,
+        }
+    }
+
+    /// Mints a sprite NFT to the owner and stores it under the base NFT.
+    fn store(&mut self, slot: EquipmentSlot) -> Pubkey {
+        let sprite = self
+            .simulator
+            .create_nft(&self.owner, &Pubkey::new_unique());
+        self.simulator
+            .process_instruction(
+                StoreSpriteBuilder::new(self.base.mint, sprite.mint, self.owner, args(slot))
+                    .instruction(),
+                &self.owner,
+            )
+            .unwrap();
+        sprite.mint
+    }
+}
+
+fn args(slot: EquipmentSlot) -> StoreSpriteArgs {
+    StoreSpriteArgs {
+        name: "Straw hat".to_string(),
+        description: "A wide-brimmed hat".to_string(),
+        perspective_tags: vec![PerspectiveTags::TopDown],
+        style_tags: vec![StyleTags::Pixel],
+        slot,
+        z_order: 3,
+        ..StoreSpriteArgs::default()
+    }
+}
+
+fn custom_error(err: TransactionError) -> (u8, SpriteManagerError) {
+    match err {
+        TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+            (index, SpriteManagerError::from_u32(code).unwrap())
+        }
+        err => panic!("Expected a custom instruction error but got {:?}", err),
+    }
+}
+
+#[test]
+fn test_sprite_flow() {
+    let mut setup = Setup::new();
+    let (escrow, _) = find_escrow_address(&setup.base.mint);
+    assert_eq!(
+        setup.simulator.account(&escrow).unwrap().owner,
+        mpl_token_metadata::id()
+    );
+    let sprite_account = setup.simulator.sprite_account(&setup.base.mint).unwrap();
+    assert_eq!(sprite_account.base_mint, setup.base.mint);
+    assert!(matches!(
+        setup.simulator.events()[..],
+        [SpriteEvent::SpriteAccountCreated { base_mint, .. }] if base_mint == setup.base.mint
+    ));
+
+    let hat = setup.store(EquipmentSlot::Hat);
+    let escrow_token_account = setup
+        .simulator
+        .account(&get_associated_token_address(&escrow, &hat))
+        .unwrap();
+    let escrow_token = spl_token::state::Account::unpack(&escrow_token_account.data).unwrap();
+    assert_eq!((escrow_token.owner, escrow_token.amount), (escrow, 1));
+    let owner_token_account = setup
+        .simulator
+        .account(&get_associated_token_address(&setup.owner, &hat))
+        .unwrap();
+    assert_eq!(
+        spl_token::state::Account::unpack(&owner_token_account.data)
+            .unwrap()
+            .amount,
+        0
+    );
+
+    setup.simulator.warp_to_slot(100);
+    setup
+        .simulator
+        .process_instruction(
+            EquipSpriteBuilder::new(setup.base.mint, hat, setup.owner).instruction(),
+            &setup.owner,
+        )
+        .unwrap();
+    let sprite_account = setup.simulator.sprite_account(&setup.base.mint).unwrap();
+    let sprite = sprite_account.find_sprite(&hat).unwrap();
+    assert!(sprite.equipped);
+    assert_eq!(sprite.updated_at.slot, 100);
+    assert!(matches!(
+        setup.simulator.events()[..],
+        [SpriteEvent::SpriteEquipped { sprite_mint, .. }] if sprite_mint == hat
+    ));
+}
+
+#[test]
+fn test_store_grows_sprite_account() {
+    let mut setup = Setup::new();
+    let (address, _) = find_sprite_address(&setup.base.mint);
+    let empty_len = setup.simulator.account(&address).unwrap().data.len();
+
+    setup.store(EquipmentSlot::Hat);
+    setup.store(EquipmentSlot::Body);
+
+    let account = setup.simulator.account(&address).unwrap();
+    assert!(account.data.len() > empty_len);
+    assert!(setup
+        .simulator
+        .rent()
+        .is_exempt(account.lamports, account.data.len()));
+    assert_eq!(
+        setup
+            .simulator
+            .sprite_account(&setup.base.mint)
+            .unwrap()
+            .sprites
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_failed_transaction_is_rolled_back() {
+    let mut simulator = Simulator::new();
+    let owner = Pubkey::new_unique();
+    simulator.airdrop(&owner, 10 * SOL);
+    let base = simulator.create_nft(&owner, &owner);
+
+    let err = simulator
+        .process_transaction(
+            &[
+                CreateSpriteAccountBuilder::new(base.mint, owner).instruction(),
+                EquipSpriteBuilder::new(base.mint, Pubkey::new_unique(), owner).instruction(),
+            ],
+            &owner,
+        )
+        .unwrap_err();
+
+    assert_eq!(custom_error(err), (1, SpriteManagerError::SpriteNotFound));
+    assert!(simulator.sprite_account(&base.mint).is_none());
+    assert_eq!(simulator.lamports(&owner), 10 * SOL);
+    assert!(simulator
+        .logs()
+        .last()
+        .unwrap()
+        .ends_with("failed: custom program error: 0x7"));
+}
+
+#[test]
+fn test_missing_signature() {
+    let mut setup = Setup::new();
+    let hat = setup.store(EquipmentSlot::Hat);
+
+    let mut instruction = EquipSpriteBuilder::new(setup.base.mint, hat, setup.owner).instruction();
+    for meta in &mut instruction.accounts {
+        meta.is_signer = false;
+    }
+
+    assert_eq!(
+        setup
+            .simulator
+            .process_instruction(instruction, &Pubkey::new_unique()),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+}
+
+#[test]
+fn test_store_fee() {
+    let mut setup = Setup::new();
+    let admin = Pubkey::new_unique();
+    let treasury = Pubkey::new_unique();
+    setup.simulator.airdrop(&admin, SOL);
+    setup.simulator.set_upgrade_authority(&admin);
+    let fees = FeeSchedule {
+        store_fee_lamports: 5_000,
+        treasury,
+        ..FeeSchedule::default()
+    };
+    setup
+        .simulator
+        .process_instruction(
+            initialize_config(
+                &sprite_manager::id(),
+                &admin,
+                ConfigLimits::default(),
+                fees.clone(),
+            ),
+            &admin,
+        )
+        .unwrap();
+
+    let sprite = setup.simulator.create_nft(&setup.owner, &admin);
+    setup
+        .simulator
+        .process_instruction(
+            StoreSpriteBuilder::new(
+                setup.base.mint,
+                sprite.mint,
+                setup.owner,
+                args(EquipmentSlot::Hat),
+            )
+            .fees(&fees)
+            .instruction(),
+            &setup.owner,
+        )
+        .unwrap();
+
+    assert_eq!(setup.simulator.lamports(&treasury), 5_000);
+    assert!(setup.simulator.events().iter().any(|event| matches!(
+        event,
+        SpriteEvent::FeeCharged {
+            lamports: 5_000,
+            ..
+        }
+    )));
+}
+
+#[test]
+fn test_sponsored_store_fee() {
+    let mut setup = Setup::new();
+    let admin = Pubkey::new_unique();
+    let treasury = Pubkey::new_unique();
+    let sponsor = Pubkey::new_unique();
+    setup.simulator.airdrop(&admin, SOL);
+    setup.simulator.airdrop(&sponsor, SOL);
+    setup.simulator.set_upgrade_authority(&admin);
+    let fees = FeeSchedule {
+        store_fee_lamports: 5_000,
+        treasury,
+        ..FeeSchedule::default()
+    };
+    setup
+        .simulator
+        .process_instruction(
+            initialize_config(
+                &sprite_manager::id(),
+                &admin,
+                ConfigLimits::default(),
+                fees.clone(),
+            ),
+            &admin,
+        )
+        .unwrap();
+
+    // A relayer pays for the transaction, the owner still pays the store fee and the rent.
+    let owner_lamports = setup.simulator.lamports(&setup.owner);
+    let sprite = setup.simulator.create_nft(&setup.owner, &admin);
+    setup
+        .simulator
+        .process_instruction(
+            StoreSpriteBuilder::new(
+                setup.base.mint,
+                sprite.mint,
+                setup.owner,
+                args(EquipmentSlot::Hat),
+            )
+            .fees(&fees)
+            .instruction(),
+            &sponsor,
+        )
+        .unwrap();
+
+    assert_eq!(setup.simulator.lamports(&treasury), 5_000);
+    assert!(setup.simulator.lamports(&setup.owner) < owner_lamports - 5_000);
+    assert_eq!(setup.simulator.lamports(&sponsor), SOL);
+}
+
+/// Writes to the data of its first account, which it may not own.
+fn write_data(_program_id: &Pubkey, accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    accounts[0].try_borrow_mut_data()?[0] = 1;
+    Ok(())
+}
+
+/// Moves a lamport from its first to its second account through the system program.
+fn transfer_lamport(_program_id: &Pubkey, accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    invoke(
+        &system_instruction::transfer(accounts[0].key, accounts[1].key, 1),
+        &accounts[..3],
+    )
+}
+
+#[test]
+fn test_runtime_rules() {
+    let mut simulator = Simulator::new();
+    let program_id = Pubkey::new_unique();
+    let account = Pubkey::new_unique();
+    simulator.set_account(
+        account,
+        solana_sdk::account::Account::new(1, 8, &Pubkey::new_unique()),
+    );
+
+    simulator.add_program(program_id, write_data);
+    assert_eq!(
+        simulator.process_instruction(
+            Instruction::new_with_bytes(program_id, &[], vec![AccountMeta::new(account, false)],),
+            &program_id
+        ),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::ExternalAccountDataModified
+        ))
+    );
+
+    // A signature cannot be passed on to a callee for an account that did not sign.
+    let (from, to) = (Pubkey::new_unique(), Pubkey::new_unique());
+    simulator.airdrop(&from, SOL);
+    simulator.add_program(program_id, transfer_lamport);
+    let transfer = |from_signs| {
+        Instruction::new_with_bytes(
+            program_id,
+            &[],
+            vec![
+                AccountMeta::new(from, from_signs),
+                AccountMeta::new(to, false),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            ],
+        )
+    };
+    assert_eq!(
+        simulator.process_instruction(transfer(false), &to),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::PrivilegeEscalation
+        ))
+    );
+    simulator
+        .process_instruction(transfer(true), &from)
+        .unwrap();
+    assert_eq!(simulator.lamports(&to), 1);
+}
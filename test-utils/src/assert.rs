@@ -14,10 +14,12 @@ macro_rules! assert_transport_error {
 macro_rules! assert_custom_error {
     ($error:expr, $matcher:pat) => {
         match $error {
-            BanksClientError::TransactionError(TransactionError::InstructionError(
-                0,
-                InstructionError::Custom(x),
-            )) => match FromPrimitive::from_i32(x as i32) {
+            $crate::solana_program_test::BanksClientError::TransactionError(
+                $crate::solana_sdk::transaction::TransactionError::InstructionError(
+                    0,
+                    $crate::solana_sdk::instruction::InstructionError::Custom(x),
+                ),
+            ) => match $crate::num_traits::FromPrimitive::from_i32(x as i32) {
                 Some($matcher) => assert!(true),
                 Some(other) => {
                     assert!(
@@ -0,0 +1,113 @@
+use crate::*;
+use solana_program::borsh::try_from_slice_unchecked;
+use spl_associated_token_account::get_associated_token_address;
+use sprite_manager::state::SpriteAccount;
+
+/// A base NFT held by the context's payer, its sprite account and the sprites stored in it.
+#[derive(Debug)]
+pub struct SpriteFixture {
+    pub base: Metadata,
+    pub base_master_edition: MasterEditionV2,
+    pub sprite_account: Pubkey,
+    pub escrow: Pubkey,
+    /// The stored sprite NFTs, in the order they were stored.
+    pub sprites: Vec<Metadata>,
+}
+
+impl SpriteFixture {
+    /// Creates a base NFT and its sprite account, then stores `count` new sprite NFTs in it
+    /// with [`sprite_args`].
+    pub async fn new(context: &mut ProgramTestContext, count: usize) -> Self {
+        let mut fixture = Self::empty(context).await;
+        for index in 0..count {
+            fixture
+                .store(context, sprite_args(index))
+                .await
+                .expect("storing the sprite should succeed");
+        }
+        fixture
+    }
+
+    /// Creates a base NFT and its sprite account without storing any sprites.
+    pub async fn empty(context: &mut ProgramTestContext) -> Self {
+        let (base, base_master_edition, _) = create_nft(context, false, None).await;
+        let (sprite_account, escrow) = create_sprite_account_helper(context, &base).await;
+
+        Self {
+            base,
+            base_master_edition,
+            sprite_account,
+            escrow,
+            sprites: vec![],
+        }
+    }
+
+    /// Mints a new sprite NFT to the payer and stores it in the sprite account.
+    pub async fn store(
+        &mut self,
+        context: &mut ProgramTestContext,
+        args: StoreSpriteArgs,
+    ) -> Result<&Metadata, BanksClientError> {
+        let (sprite, _, _) = create_nft(context, false, None).await;
+        store_sprite_helper(context, &self.base, &sprite, args).await?;
+        self.sprites.push(sprite);
+        Ok(self.sprites.last().unwrap())
+    }
+
+    pub async fn get_data(&self, context: &mut ProgramTestContext) -> SpriteAccount {
+        let account = get_account(context, &self.sprite_account).await;
+        try_from_slice_unchecked(&account.data).unwrap()
+    }
+
+    /// The escrow's associated token account for `sprite`.
+    pub fn escrow_token_account(&self, sprite: &Metadata) -> Pubkey {
+        get_associated_token_address(&self.escrow, &sprite.mint.pubkey())
+    }
+
+    /// Asserts that the escrow exists and holds the token of every stored sprite, and that the
+    /// token accounts they came from are empty.
+    pub async fn assert_escrowed(&self, context: &mut ProgramTestContext) {
+        let escrow = get_account(context, &self.escrow).await;
+        assert_eq!(escrow.owner, mpl_token_metadata::id());
+
+        for sprite in &self.sprites {
+            let account = get_account(context, &self.escrow_token_account(sprite)).await;
+            let token = spl_token::state::Account::unpack(&account.data).unwrap();
+            assert_eq!(token.mint, sprite.mint.pubkey());
+            assert_eq!(token.owner, self.escrow);
+            assert_eq!(token.amount, 1);
+
+            let account = get_account(context, &sprite.token.pubkey()).await;
+            let token = spl_token::state::Account::unpack(&account.data).unwrap();
+            assert_eq!(token.amount, 0);
+        }
+    }
+
+    /// Asserts that the sprite account belongs to the base NFT and lists exactly the stored
+    /// sprites, in order.
+    pub async fn assert_stored(&self, context: &mut ProgramTestContext) {
+        let sprite_account = self.get_data(context).await;
+        assert_eq!(sprite_account.base_mint, self.base.mint.pubkey());
+
+        let stored: Vec<_> = sprite_account
+            .sprites
+            .iter()
+            .map(|sprite| sprite.mint)
+            .collect();
+        let expected: Vec<_> = self
+            .sprites
+            .iter()
+            .map(|sprite| sprite.mint.pubkey())
+            .collect();
+        assert_eq!(stored, expected);
+    }
+}
+
+/// Arguments for an untagged body sprite named after `index`.
+pub fn sprite_args(index: usize) -> StoreSpriteArgs {
+    StoreSpriteArgs {
+        name: format!("sprite {}", index),
+        description: "a sprite".to_string(),
+        ..StoreSpriteArgs::default()
+    }
+}
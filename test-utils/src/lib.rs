@@ -1,21 +1,58 @@
+//! `solana-program-test` helpers for testing against the sprite manager.
+//!
+//! Besides token metadata helpers for minting NFTs and editions, [`SpriteFixture`] sets up a base
+//! NFT with a sprite account and stored sprites in one call and asserts on the resulting state:
+//!
+//! ```ignore
+//! let mut context = program_test().start_with_context().await;
+//! let fixture = SpriteFixture::new(&mut context, 3).await;
+//! fixture.assert_stored(&mut context).await;
+//! fixture.assert_escrowed(&mut context).await;
+//! ```
+//!
+//! [`program_test`] loads `sprite_manager.so` and `mpl_token_metadata.so`, so both must be in
+//! `BPF_OUT_DIR` or the `tests/fixtures` directory of the crate running the tests.
+
+// The helpers take the arguments of the token metadata instructions they wrap.
+#![allow(clippy::too_many_arguments)]
+
 mod assert;
 mod edition_marker;
+mod fixture;
 mod master_edition_v2;
 mod metadata;
 
-pub use assert::*;
 pub use edition_marker::EditionMarker;
+pub use fixture::{sprite_args, SpriteFixture};
 pub use master_edition_v2::MasterEditionV2;
 pub use metadata::{assert_collection_size, Metadata};
 pub use mpl_token_metadata::instruction;
-use mpl_token_metadata::state::{Collection, CollectionDetails, EscrowAuthority};
+use mpl_token_metadata::state::{Collection, CollectionDetails, Creator};
 use solana_program_test::*;
 use solana_sdk::{
-    account::Account, program_pack::Pack, pubkey::Pubkey, signature::Signer,
-    signer::keypair::Keypair, system_instruction, transaction::Transaction,
+    account::{Account, AccountSharedData},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Signer,
+    signer::keypair::Keypair,
+    system_instruction,
+    transaction::Transaction,
 };
 use spl_token::state::Mint;
-use sprite_manager::{instruction::*, pda::find_sprite_address};
+use sprite_manager::{
+    builders::{CreateSpriteAccountBuilder, StoreSpriteBuilder},
+    instruction::*,
+    pda::{
+        find_config_address, find_escrow_address, find_program_data_address, find_sprite_address,
+    },
+    state::{FeeSchedule, ProgramConfig, SolanaAccount},
+};
+
+// Used by the exported assertion macros.
+#[doc(hidden)]
+pub use {num_traits, solana_program_test, solana_sdk};
 
 pub const DEFAULT_COLLECTION_DETAILS: Option<CollectionDetails> =
     Some(CollectionDetails::V1 { size: 0 });
@@ -220,30 +257,33 @@ pub async fn create_mint(
     context.banks_client.process_transaction(tx).await
 }
 
+/// Records `authority` as the sprite manager's upgrade authority, the only signer
+/// `InitializeConfig` accepts. `ProgramTest` deploys programs with the non-upgradeable loader, so
+/// the ProgramData account is written directly.
+pub fn set_upgrade_authority(context: &mut ProgramTestContext, authority: &Pubkey) {
+    let state = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*authority),
+    };
+    let lamports =
+        Rent::default().minimum_balance(UpgradeableLoaderState::size_of_programdata_metadata());
+    let account =
+        AccountSharedData::new_data(lamports, &state, &bpf_loader_upgradeable::id()).unwrap();
+    context.set_account(&find_program_data_address().0, &account);
+}
+
 /// metadata is used as the Base NFT for the Trifle's Escrow account.
-/// master_edition is the edition of the Base NFT
 pub async fn create_sprite_account_helper(
     context: &mut ProgramTestContext,
     metadata: &Metadata,
-    master_edition: &MasterEditionV2,
 ) -> (Pubkey, Pubkey) {
     let (sprite_addr, _) = find_sprite_address(&metadata.mint.pubkey());
+    let (escrow_addr, _) = find_escrow_address(&metadata.mint.pubkey());
 
-    let (escrow_addr, _) = mpl_token_metadata::processor::find_escrow_account(
-        &metadata.mint.pubkey(),
-        &EscrowAuthority::Creator(sprite_addr.to_owned()),
-    );
-
-    let create_sprite_account_ix = create_sprite_account(
-        &sprite_manager::id(),
-        &escrow_addr,
-        &metadata.pubkey,
-        &metadata.mint.pubkey(),
-        &metadata.token.pubkey(),
-        &master_edition.pubkey,
-        &sprite_addr,
-        &context.payer.pubkey(),
-    );
+    let create_sprite_account_ix =
+        CreateSpriteAccountBuilder::new(metadata.mint.pubkey(), context.payer.pubkey())
+            .base_token_account(metadata.token.pubkey())
+            .instruction();
 
     let tx = Transaction::new_signed_with_payer(
         &[create_sprite_account_ix],
@@ -257,6 +297,44 @@ pub async fn create_sprite_account_helper(
     (sprite_addr, escrow_addr)
 }
 
+/// Transfers the token held in `sprite.token` into the escrow of `base` and records it.
+pub async fn store_sprite_helper(
+    context: &mut ProgramTestContext,
+    base: &Metadata,
+    sprite: &Metadata,
+    args: StoreSpriteArgs,
+) -> Result<(), BanksClientError> {
+    // Fees go to the configured treasury, token fees between the payer's and treasury's ATAs.
+    let fees = match context
+        .banks_client
+        .get_account(find_config_address().0)
+        .await
+        .unwrap()
+    {
+        Some(config) => ProgramConfig::safe_deserialize(&config.data).unwrap().fees,
+        None => FeeSchedule::default(),
+    };
+
+    let store_ix = StoreSpriteBuilder::new(
+        base.mint.pubkey(),
+        sprite.mint.pubkey(),
+        context.payer.pubkey(),
+        args,
+    )
+    .sprite_token_account(sprite.token.pubkey())
+    .fees(&fees)
+    .instruction();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[store_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
 pub async fn create_nft(
     context: &mut ProgramTestContext,
     create_collection: bool,
@@ -295,7 +373,11 @@ pub async fn create_nft(
                 "Test".to_string(),
                 "TST".to_string(),
                 "uri".to_string(),
-                None,
+                Some(vec![Creator {
+                    address: context.payer.pubkey(),
+                    verified: true,
+                    share: 100,
+                }]),
                 10,
                 true,
                 Some(Collection {
@@ -341,7 +423,11 @@ pub async fn create_nft(
                 "Test".to_string(),
                 "TST".to_string(),
                 "uri".to_string(),
-                None,
+                Some(vec![Creator {
+                    address: context.payer.pubkey(),
+                    verified: true,
+                    share: 100,
+                }]),
                 10,
                 true,
                 None,
@@ -393,7 +479,11 @@ pub async fn create_sft(
                 "Test".to_string(),
                 "TST".to_string(),
                 "uri".to_string(),
-                None,
+                Some(vec![Creator {
+                    address: context.payer.pubkey(),
+                    verified: true,
+                    share: 100,
+                }]),
                 10,
                 true,
                 Some(Collection {
@@ -436,7 +526,11 @@ pub async fn create_sft(
                 "Test".to_string(),
                 "TST".to_string(),
                 "uri".to_string(),
-                None,
+                Some(vec![Creator {
+                    address: context.payer.pubkey(),
+                    verified: true,
+                    share: 100,
+                }]),
                 10,
                 true,
                 None,
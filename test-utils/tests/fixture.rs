@@ -0,0 +1,59 @@
+#![cfg(feature = "test-bpf")]
+
+use solana_program_test::*;
+use solana_sdk::signer::Signer;
+use sprite_manager::{
+    error::SpriteManagerError,
+    instruction::StoreSpriteArgs,
+    state::{ConfigLimits, StyleTags},
+};
+use sprite_manager_test_utils::*;
+
+#[tokio::test]
+async fn test_sprite_fixture() {
+    let mut context = program_test().start_with_context().await;
+
+    let fixture = SpriteFixture::new(&mut context, 3).await;
+    fixture.assert_stored(&mut context).await;
+    fixture.assert_escrowed(&mut context).await;
+
+    let sprite_account = fixture.get_data(&mut context).await;
+    assert_eq!(sprite_account.sprites[2].name, "sprite 2");
+    assert!(sprite_account
+        .sprites
+        .iter()
+        .all(|sprite| sprite.artist == context.payer.pubkey()));
+}
+
+#[tokio::test]
+async fn test_failed_store_is_not_recorded() {
+    let mut context = program_test().start_with_context().await;
+
+    let mut fixture = SpriteFixture::empty(&mut context).await;
+    fixture
+        .store(
+            &mut context,
+            StoreSpriteArgs {
+                style_tags: vec![StyleTags::Pixel],
+                ..sprite_args(0)
+            },
+        )
+        .await
+        .unwrap();
+
+    let err = fixture
+        .store(
+            &mut context,
+            StoreSpriteArgs {
+                name: "x".repeat(ConfigLimits::default().max_name_len as usize + 1),
+                ..sprite_args(1)
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_custom_error!(err, SpriteManagerError::LimitExceeded);
+
+    assert_eq!(fixture.sprites.len(), 1);
+    fixture.assert_stored(&mut context).await;
+    fixture.assert_escrowed(&mut context).await;
+}